@@ -0,0 +1,8 @@
+#![no_main]
+#[macro_use]
+extern crate libfuzzer_sys;
+extern crate purse_fuzz;
+
+fuzz_target!(|data: &[u8]| {
+    purse_fuzz::run_list_ops(data);
+});
@@ -0,0 +1,213 @@
+//! Differential oracle for fuzzing purse collections.
+//!
+//! Every operation is applied both to a purse structure and to a plain `Vec` model, and the two
+//! are compared after each step. A small pool of versions is kept around so that operations run
+//! against lists that share structure with each other.
+
+extern crate purse;
+
+use std::mem;
+use std::sync::{Arc, Barrier};
+use std::thread;
+
+use purse::List;
+
+const POOL_SIZE: usize = 4;
+
+/// Checks that `list` holds exactly the elements of `model`, through every read path.
+pub fn assert_list_eq(list: &List<u8>, model: &[u8]) {
+    assert_eq!(list.len(), model.len());
+    assert_eq!(list.first(), model.first());
+    assert_eq!(list.last(), model.last());
+
+    for (i, elem) in model.iter().enumerate() {
+        assert_eq!(&list[i], elem);
+    }
+
+    let collected: Vec<u8> = list.clone().into_iter().collect();
+
+    assert_eq!(collected, model);
+}
+
+/// Interprets `data` as a sequence of list operations and checks each result against the model.
+pub fn run_list_ops(data: &[u8]) {
+    let mut pool: Vec<(List<u8>, Vec<u8>)> = vec![(List::empty(), Vec::new()); POOL_SIZE];
+    let mut bytes = data.iter().cloned();
+
+    while let (Some(op), Some(arg)) = (bytes.next(), bytes.next()) {
+        let dst = (op as usize >> 4) % POOL_SIZE;
+        let src = arg as usize % POOL_SIZE;
+        let (list, model) = pool[src].clone();
+        let index = arg as usize / POOL_SIZE;
+
+        let result = match op % 11 {
+            0 => {
+                let mut model = model;
+                model.insert(0, arg);
+
+                (list.prepend(arg), model)
+            }
+            1 => {
+                let mut model = model;
+                model.push(arg);
+
+                (list.append(arg), model)
+            }
+            2 => {
+                let (right, right_model) = pool[dst].clone();
+                let mut model = model;
+                model.extend(right_model);
+
+                (list.concat(&right), model)
+            }
+            3 => {
+                let mut model = model;
+                model.insert(0, arg);
+
+                (List::create(arg, list), model)
+            }
+            4 => {
+                // split the pooled list itself, so the nodes before `at` may be unshared and the
+                // link to the rest gets cut
+                drop((list, model));
+
+                let (mut front, mut front_model) = mem::take(&mut pool[src]);
+                let at = index % (front_model.len() + 1);
+                let back = front.split_off(at);
+                let back_model = front_model.split_off(at);
+
+                pool[src] = (front, front_model);
+
+                (back, back_model)
+            }
+            5 => {
+                let at = index % (model.len() + 1);
+                let updated = list.update(at, arg);
+
+                if at == model.len() {
+                    assert!(updated.is_none());
+
+                    (list, model)
+                } else {
+                    let mut model = model;
+                    model[at] = arg;
+
+                    (updated.unwrap(), model)
+                }
+            }
+            6 => {
+                let at = index % (model.len() + 2);
+                let inserted = list.insert(at, arg);
+
+                if at > model.len() {
+                    assert!(inserted.is_none());
+
+                    (list, model)
+                } else {
+                    let mut model = model;
+                    model.insert(at, arg);
+
+                    (inserted.unwrap(), model)
+                }
+            }
+            7 => {
+                let at = index % (model.len() + 1);
+                let removed = list.remove(at);
+
+                if at == model.len() {
+                    assert!(removed.is_none());
+
+                    (list, model)
+                } else {
+                    let mut model = model;
+                    model.remove(at);
+
+                    (removed.unwrap(), model)
+                }
+            }
+            8 => {
+                let keep = |elem: &u8| elem % 3 != arg % 3;
+                let model: Vec<u8> = model.iter().cloned().filter(keep).collect();
+
+                (list.filter(keep), model)
+            }
+            9 => {
+                // concat two versions onto each other from two threads at once, so both race to
+                // claim each other's tail
+                let (right, right_model) = pool[dst].clone();
+                let barrier = Arc::new(Barrier::new(2));
+                let handle = {
+                    let (left, right, barrier) = (list.clone(), right.clone(), barrier.clone());
+
+                    thread::spawn(move || {
+                        barrier.wait();
+                        right.concat(&left)
+                    })
+                };
+
+                barrier.wait();
+
+                let joined = list.concat(&right);
+                let reversed = handle.join().unwrap();
+                let mut reversed_model = right_model.clone();
+                reversed_model.extend(model.iter().cloned());
+                let mut model = model;
+                model.extend(right_model);
+
+                pool[src] = (reversed, reversed_model);
+
+                (joined, model)
+            }
+            _ => (List::empty(), Vec::new()),
+        };
+
+        pool[dst] = result;
+
+        for &(ref list, ref model) in &pool {
+            assert_list_eq(list, model);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_list_ops;
+
+    // short hand-written sequences that reach every operation
+    const SEEDS: &[&[u8]] = &[
+        &[0, 1, 0, 2, 1, 3],
+        &[1, 5, 1, 6, 17, 0, 2, 0, 34, 1],
+        &[0, 9, 0, 8, 0, 7, 4, 8, 4, 0, 20, 4],
+        &[1, 1, 1, 2, 1, 3, 5, 4, 5, 16, 5, 12],
+        &[1, 1, 1, 2, 6, 4, 6, 8, 6, 12, 22, 0],
+        &[1, 1, 1, 2, 1, 3, 7, 0, 7, 4, 7, 8],
+        &[1, 3, 1, 4, 1, 5, 1, 6, 8, 0, 8, 1],
+        &[1, 1, 17, 1, 9, 1, 9, 1, 25, 2, 10, 0],
+    ];
+
+    #[test]
+    fn seed_corpus() {
+        for seed in SEEDS {
+            run_list_ops(seed);
+        }
+    }
+
+    // pseudo-random sequences from a fixed seed
+    #[test]
+    fn generated_corpus() {
+        let mut state: u64 = 0x2545_f491_4f6c_dd1d;
+
+        for _ in 0..2000 {
+            let data: Vec<u8> = (0..64)
+                .map(|_| {
+                    state ^= state << 13;
+                    state ^= state >> 7;
+                    state ^= state << 17;
+                    state as u8
+                })
+                .collect();
+
+            run_list_ops(&data);
+        }
+    }
+}
@@ -0,0 +1,220 @@
+use std::fmt;
+
+use List;
+use instrument;
+use list::iterator;
+
+/// A persistent association list: a list of key-value bindings where newer bindings shadow older
+/// ones with the same key.
+///
+/// Lookups are linear, which makes this a good fit for small environments (e.g. the variable
+/// scopes of an interpreter) where a hash map would be overkill. Inserting is O(1) and shares the
+/// whole previous list.
+///
+/// Examples
+///
+/// ```
+/// use purse::AssocList;
+///
+/// let globals = AssocList::empty().insert("x", 1).insert("y", 2);
+/// let locals = globals.insert("x", 10);
+///
+/// assert_eq!(locals.get(&"x"), Some(&10));
+/// assert_eq!(locals.get(&"y"), Some(&2));
+/// assert_eq!(globals.get(&"x"), Some(&1));
+/// ```
+#[derive(Clone)]
+pub struct AssocList<K: Clone, V: Clone> {
+    bindings: List<(K, V)>,
+}
+
+impl<K: Clone + PartialEq, V: Clone> AssocList<K, V> {
+    /// Creates an association list without any bindings.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::AssocList;
+    ///
+    /// let env: AssocList<&str, i32> = AssocList::empty();
+    ///
+    /// assert!(env.is_empty());
+    /// ```
+    pub const fn empty() -> Self {
+        AssocList { bindings: List::empty() }
+    }
+
+    /// Creates a new association list with a binding from `key` to `value` that shadows any
+    /// existing binding for the same key.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::AssocList;
+    ///
+    /// let env = AssocList::empty().insert('a', 1);
+    /// let shadowed = env.insert('a', 2);
+    ///
+    /// assert_eq!(env.get(&'a'), Some(&1));
+    /// assert_eq!(shadowed.get(&'a'), Some(&2));
+    /// ```
+    pub fn insert(&self, key: K, value: V) -> Self {
+        AssocList { bindings: self.bindings.prepend((key, value)) }
+    }
+
+    /// Returns a reference to the value of the most recent binding for `key`, or None if the key
+    /// isn't bound.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::AssocList;
+    ///
+    /// let env = AssocList::empty().insert("one", 1);
+    ///
+    /// assert_eq!(env.get(&"one"), Some(&1));
+    /// assert_eq!(env.get(&"two"), None);
+    /// ```
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.0 == *key)
+            .map(|binding| &binding.1)
+    }
+
+    /// Returns true if `key` is bound.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::AssocList;
+    ///
+    /// let env = AssocList::empty().insert("one", 1);
+    ///
+    /// assert!(env.contains_key(&"one"));
+    /// assert!(!env.contains_key(&"two"));
+    /// ```
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Creates a new association list without any bindings for `key`, including shadowed ones.
+    /// The bindings after the oldest binding for `key` are shared with the original list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::AssocList;
+    ///
+    /// let env = AssocList::empty().insert("x", 1).insert("y", 2).insert("x", 3);
+    /// let removed = env.remove(&"x");
+    ///
+    /// assert_eq!(removed.get(&"x"), None);
+    /// assert_eq!(removed.get(&"y"), Some(&2));
+    /// assert_eq!(env.get(&"x"), Some(&3));
+    /// ```
+    pub fn remove(&self, key: &K) -> Self {
+        let last = self.bindings
+            .iter()
+            .enumerate()
+            .filter(|&(_, binding)| binding.0 == *key)
+            .map(|(index, _)| index)
+            .last();
+
+        let last = match last {
+            Some(index) => index,
+            None => return self.clone(),
+        };
+
+        let prefix: List<(K, V)> = self.bindings
+            .iter()
+            .take(last)
+            .filter(|binding| binding.0 != *key)
+            .map(instrument::clone_counted)
+            .collect();
+
+        AssocList { bindings: prefix.concat(&self.bindings.suffix(last + 1)) }
+    }
+
+    /// Returns the number of live (unshadowed) bindings. This walks `iter`, so it takes quadratic
+    /// time in the number of bindings.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::AssocList;
+    ///
+    /// let env = AssocList::empty().insert(1, 'a').insert(2, 'b').insert(1, 'c');
+    ///
+    /// assert_eq!(env.len(), 2);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.iter().count()
+    }
+
+    /// Returns true if there are no bindings.
+    pub fn is_empty(&self) -> bool {
+        self.bindings.is_empty()
+    }
+
+    /// Returns an iterator over the live bindings, most recent first. Bindings shadowed by a newer
+    /// binding for the same key are skipped.
+    ///
+    /// Keys only need `PartialEq`, so each binding is compared against every live key returned
+    /// before it, and a full iteration takes quadratic time in the number of bindings.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::AssocList;
+    ///
+    /// let env = AssocList::empty().insert("x", 1).insert("y", 2).insert("x", 3);
+    /// let live: Vec<_> = env.iter().collect();
+    ///
+    /// assert_eq!(live, vec![(&"x", &3), (&"y", &2)]);
+    /// ```
+    pub fn iter(&self) -> Bindings<'_, K, V> {
+        Bindings {
+            bindings: self.bindings.iter(),
+            seen: Vec::new(),
+        }
+    }
+}
+
+impl<K: Clone + PartialEq, V: Clone> Default for AssocList<K, V> {
+    fn default() -> Self {
+        AssocList::empty()
+    }
+}
+
+impl<K, V> fmt::Debug for AssocList<K, V>
+where
+    K: Clone + PartialEq + fmt::Debug,
+    V: Clone + fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+/// An iterator over the live bindings of an `AssocList`, created by `AssocList::iter`.
+pub struct Bindings<'a, K: 'a + Clone, V: 'a + Clone> {
+    bindings: iterator::Iter<'a, (K, V)>,
+    seen: Vec<&'a K>,
+}
+
+impl<'a, K: 'a + Clone + PartialEq, V: 'a + Clone> Iterator for Bindings<'a, K, V> {
+    type Item = (&'a K, &'a V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        for binding in &mut self.bindings {
+            if !self.seen.contains(&&binding.0) {
+                self.seen.push(&binding.0);
+
+                return Some((&binding.0, &binding.1));
+            }
+        }
+
+        None
+    }
+}
@@ -0,0 +1,178 @@
+//! Counters for finding out where persistent code ends up copying.
+//!
+//! With the `instrument` feature enabled, the crate counts every node it allocates, every element
+//! it clones internally and every operation that copies part of a list instead of sharing it, like
+//! a `concat` onto a tail that's already in use or the prefix that `update` has to rebuild. With
+//! the `tracing` feature, each such copy also emits a `tracing` debug event.
+//!
+//! The counters are global and shared by all threads.
+//!
+//! In debug builds, `set_copy_limit` turns copies above a given fraction of the resulting list
+//! into panics that point at the offending call, to help keep update paths incremental.
+
+#[cfg(feature = "instrument")]
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+#[cfg(feature = "instrument")]
+use std::panic::Location;
+
+#[cfg(feature = "instrument")]
+static NODE_ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "instrument")]
+static ELEMENT_CLONES: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "instrument")]
+static DEEP_COPIES: AtomicUsize = AtomicUsize::new(0);
+// the bits of an f64; copying at most the whole result is always allowed by default
+#[cfg(feature = "instrument")]
+static COPY_LIMIT: AtomicU64 = AtomicU64::new(0x3ff0_0000_0000_0000);
+
+/// A snapshot of the instrumentation counters.
+#[cfg(feature = "instrument")]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Stats {
+    /// Number of list nodes allocated.
+    pub node_allocations: usize,
+    /// Number of elements cloned by the crate, e.g. when copying the unshared part of a list, when
+    /// building a new list from an existing one's elements, or by owning iterators.
+    pub element_clones: usize,
+    /// Number of operations that copied elements of a list because they couldn't be shared.
+    pub deep_copies: usize,
+}
+
+/// Returns the current value of the counters.
+///
+/// #Examples
+///
+/// ```
+/// # #[macro_use] extern crate purse;
+/// # fn main() {
+/// use purse::instrument;
+///
+/// let list = purse_list![1, 2, 3];
+///
+/// instrument::reset();
+///
+/// // the second concat can't extend the shared tail in place, so it copies `list`
+/// let _a = list.clone().concat(&purse_list![4]);
+/// let _b = list.clone().concat(&purse_list![5]);
+///
+/// let stats = instrument::stats();
+///
+/// assert_eq!(stats.deep_copies, 1);
+/// assert_eq!(stats.element_clones, 3);
+///
+/// // updating the last element copies the two before it
+/// let _c = list.update(2, 30).unwrap();
+///
+/// assert_eq!(instrument::stats().deep_copies, 2);
+/// assert_eq!(instrument::stats().element_clones, 3 + 2);
+/// # }
+/// ```
+#[cfg(feature = "instrument")]
+pub fn stats() -> Stats {
+    Stats {
+        node_allocations: NODE_ALLOCATIONS.load(Ordering::Relaxed),
+        element_clones: ELEMENT_CLONES.load(Ordering::Relaxed),
+        deep_copies: DEEP_COPIES.load(Ordering::Relaxed),
+    }
+}
+
+/// Sets all the counters back to zero.
+#[cfg(feature = "instrument")]
+pub fn reset() {
+    NODE_ALLOCATIONS.store(0, Ordering::Relaxed);
+    ELEMENT_CLONES.store(0, Ordering::Relaxed);
+    DEEP_COPIES.store(0, Ordering::Relaxed);
+}
+
+/// Sets the largest fraction of a resulting list that an operation may copy before it's treated
+/// as a bug. Only has an effect in builds with debug assertions enabled, where exceeding the limit
+/// panics with the location of the call that made the copy. A limit of `1.0` or more disables the
+/// check, which is the default.
+///
+/// #Examples
+///
+/// ```
+/// # #[macro_use] extern crate purse;
+/// # fn main() {
+/// use purse::instrument;
+/// use std::panic;
+///
+/// let list = purse_list![1, 2, 3];
+/// let _extended = list.clone().concat(&purse_list![4]);
+///
+/// instrument::set_copy_limit(0.5);
+///
+/// // three of the four resulting elements would be copied
+/// let result = panic::catch_unwind(|| list.clone().concat(&purse_list![5]));
+///
+/// assert!(result.is_err());
+///
+/// // updating the last element rebuilds the two before it, updating the first one shares the rest
+/// assert!(panic::catch_unwind(|| list.update(2, 30)).is_err());
+/// assert_eq!(list.update(0, 10), Some(purse_list![10, 2, 3]));
+///
+/// instrument::set_copy_limit(1.0);
+///
+/// assert_eq!(list.concat(&purse_list![5]), purse_list![1, 2, 3, 5]);
+/// # }
+/// ```
+#[cfg(feature = "instrument")]
+pub fn set_copy_limit(fraction: f64) {
+    COPY_LIMIT.store(fraction.to_bits(), Ordering::Relaxed);
+}
+
+/// Returns the fraction set with `set_copy_limit`.
+#[cfg(feature = "instrument")]
+pub fn copy_limit() -> f64 {
+    f64::from_bits(COPY_LIMIT.load(Ordering::Relaxed))
+}
+
+#[inline]
+pub(crate) fn node_allocated() {
+    #[cfg(feature = "instrument")]
+    NODE_ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+}
+
+#[inline]
+pub(crate) fn element_cloned() {
+    #[cfg(feature = "instrument")]
+    ELEMENT_CLONES.fetch_add(1, Ordering::Relaxed);
+}
+
+// Clones an element, counting the clone.
+#[inline]
+pub(crate) fn clone_counted<T: Clone>(data: &T) -> T {
+    element_cloned();
+
+    data.clone()
+}
+
+#[inline]
+#[track_caller]
+pub(crate) fn deep_copied(operation: &'static str, copied: usize, total: usize) {
+    #[cfg(feature = "instrument")]
+    {
+        DEEP_COPIES.fetch_add(1, Ordering::Relaxed);
+
+        #[cfg(feature = "tracing")]
+        ::tracing::debug!(
+            operation,
+            copied,
+            total,
+            location = %Location::caller(),
+            "purse: copied list instead of sharing it"
+        );
+
+        if cfg!(debug_assertions) && copied as f64 > copy_limit() * total as f64 {
+            panic!(
+                "purse: {} copied {} of {} elements at {}",
+                operation,
+                copied,
+                total,
+                Location::caller()
+            );
+        }
+    }
+
+    let _ = (operation, copied, total);
+}
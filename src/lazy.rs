@@ -0,0 +1,152 @@
+use std::fmt;
+use std::ops::Deref;
+use std::sync::{Arc, Mutex, OnceLock};
+
+type Thunk<T> = Box<dyn FnOnce() -> T + Send>;
+
+/// A lazily computed value that is evaluated at most once, the first time it's needed.
+///
+/// Clones share the same value, so a `Lazy` can be stored in persistent structures and forced
+/// from any of their versions or threads; the computation still only runs once.
+///
+/// Examples
+///
+/// ```
+/// use purse::Lazy;
+/// use std::sync::Arc;
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use std::thread;
+///
+/// let runs = Arc::new(AtomicUsize::new(0));
+/// let counter = runs.clone();
+/// let lazy = Lazy::new(move || {
+///     counter.fetch_add(1, Ordering::SeqCst);
+///     6 * 7
+/// });
+///
+/// let handles: Vec<_> = (0..4)
+///     .map(|_| {
+///         let lazy = lazy.clone();
+///         thread::spawn(move || *lazy.force())
+///     })
+///     .collect();
+///
+/// for handle in handles {
+///     assert_eq!(handle.join().unwrap(), 42);
+/// }
+///
+/// assert_eq!(runs.load(Ordering::SeqCst), 1);
+/// ```
+pub struct Lazy<T> {
+    inner: Arc<Inner<T>>,
+}
+
+struct Inner<T> {
+    value: OnceLock<T>,
+    thunk: Mutex<Option<Thunk<T>>>,
+}
+
+impl<T> Lazy<T> {
+    /// Creates a value that will be computed by calling `f` when it's first forced.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::Lazy;
+    ///
+    /// let lazy = Lazy::new(|| "computed".to_string());
+    ///
+    /// assert!(!lazy.is_evaluated());
+    /// assert_eq!(*lazy, "computed");
+    /// ```
+    pub fn new<F>(f: F) -> Self
+    where
+        F: FnOnce() -> T + Send + 'static,
+    {
+        Lazy {
+            inner: Arc::new(Inner {
+                value: OnceLock::new(),
+                thunk: Mutex::new(Some(Box::new(f))),
+            }),
+        }
+    }
+
+    /// Creates a value that has already been evaluated.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::Lazy;
+    ///
+    /// let lazy = Lazy::evaluated(1);
+    ///
+    /// assert!(lazy.is_evaluated());
+    /// assert_eq!(*lazy.force(), 1);
+    /// ```
+    pub fn evaluated(value: T) -> Self {
+        Lazy {
+            inner: Arc::new(Inner {
+                value: OnceLock::from(value),
+                thunk: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Returns a reference to the value, computing it first if no clone of this `Lazy` has done
+    /// so yet. If several threads force the value at the same time, one of them computes it and
+    /// the others wait for the result.
+    ///
+    /// #Panics
+    ///
+    /// Panics if the computation panicked during an earlier call.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::Lazy;
+    ///
+    /// let lazy = Lazy::new(|| vec![1, 2, 3]);
+    ///
+    /// assert_eq!(lazy.force().len(), 3);
+    /// assert!(lazy.is_evaluated());
+    /// ```
+    pub fn force(&self) -> &T {
+        self.inner.value.get_or_init(|| {
+            let thunk = self.inner
+                .thunk
+                .lock()
+                .unwrap_or_else(|poisoned| poisoned.into_inner())
+                .take();
+
+            thunk.expect("Lazy value panicked while being evaluated")()
+        })
+    }
+
+    /// Returns true if the value has been computed.
+    pub fn is_evaluated(&self) -> bool {
+        self.inner.value.get().is_some()
+    }
+}
+
+impl<T> Clone for Lazy<T> {
+    fn clone(&self) -> Self {
+        Lazy { inner: self.inner.clone() }
+    }
+}
+
+impl<T> Deref for Lazy<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        self.force()
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for Lazy<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.inner.value.get() {
+            Some(value) => f.debug_tuple("Lazy").field(value).finish(),
+            None => write!(f, "Lazy(<unevaluated>)"),
+        }
+    }
+}
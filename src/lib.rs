@@ -1,10 +1,87 @@
+#[cfg(feature = "triomphe")]
+extern crate triomphe;
+#[cfg(feature = "schemars")]
+extern crate schemars;
+#[cfg(feature = "tracing")]
+extern crate tracing;
+#[cfg(feature = "rand")]
+extern crate rand;
+
 mod list;
+mod assoc_list;
+mod range_set;
+mod lazy;
+#[cfg(feature = "instrument")]
+pub mod instrument;
+#[cfg(not(feature = "instrument"))]
+mod instrument;
+pub mod prelude;
 
 pub use list::list::List;
+pub use list::iterator::{IntoIter, Iter};
+pub use list::compare::Comparison;
+pub use list::merge::Conflict;
+pub use list::unique::KeepPolicy;
+pub use list::walker::{Walker, NodeRef, Garbage};
+pub use assoc_list::{AssocList, Bindings};
+pub use range_set::RangeSet;
+pub use lazy::Lazy;
 
 #[cfg(test)]
 mod tests {
+    use RangeSet;
+
     #[test]
     fn it_works() {
     }
+
+    #[test]
+    fn range_set_matches_model() {
+        let ops = [
+            (true, 3, 9),
+            (true, 12, 15),
+            (true, 9, 12),
+            (false, 5, 6),
+            (true, 20, 30),
+            (false, 0, 4),
+            (true, 0, 2),
+            (false, 14, 25),
+            (true, 2, 3),
+            (false, 28, 40),
+            (true, 1, 1),
+            (false, 7, 7),
+        ];
+        let mut set = RangeSet::empty();
+        let mut model = [false; 40];
+        let mut versions = Vec::new();
+
+        for &(insert, start, end) in ops.iter() {
+            versions.push((set.clone(), model));
+
+            set = if insert {
+                set.insert_range(start..end)
+            } else {
+                set.remove_range(start..end)
+            };
+
+            for value in model[start..end].iter_mut() {
+                *value = insert;
+            }
+
+            let ranges: Vec<_> = set.iter().cloned().collect();
+
+            for pair in ranges.windows(2) {
+                assert!(pair[0].start < pair[0].end && pair[0].end < pair[1].start);
+            }
+            for (value, &present) in model.iter().enumerate() {
+                assert_eq!(set.contains(&value), present);
+            }
+        }
+
+        for (version, model) in versions {
+            for (value, &present) in model.iter().enumerate() {
+                assert_eq!(version.contains(&value), present);
+            }
+        }
+    }
 }
@@ -0,0 +1,112 @@
+use List;
+
+use super::node::Arc;
+
+/// How two lists relate, returned by `List::cmp_structure`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Comparison {
+    /// The lists are made of the same nodes, so one is a copy of the other.
+    Identical,
+    /// The lists have equal elements but don't share all of their nodes.
+    Equal,
+    /// The lists have different elements or lengths.
+    Different,
+}
+
+impl<T: Clone + PartialEq> List<T> {
+    /// Compares the lists element by element, without looking at which nodes they share. The
+    /// same as `==`.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1.0, 2.0];
+    ///
+    /// assert!(list.eq_deep(&purse_list![1.0, 2.0]));
+    /// assert!(!purse_list![f64::NAN].eq_deep(&purse_list![f64::NAN]));
+    /// # }
+    /// ```
+    pub fn eq_deep(&self, other: &Self) -> bool {
+        self == other
+    }
+
+    /// Returns whether the lists share all of their nodes, have equal elements without sharing
+    /// all of them, or are different. Identical lists are found in O(1); otherwise the elements
+    /// are compared one by one.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::Comparison;
+    ///
+    /// let list = purse_list![1, 2];
+    ///
+    /// assert_eq!(list.cmp_structure(&list.clone()), Comparison::Identical);
+    /// assert_eq!(list.cmp_structure(&purse_list![1, 2]), Comparison::Equal);
+    /// assert_eq!(list.cmp_structure(&purse_list![1]), Comparison::Different);
+    /// # }
+    /// ```
+    pub fn cmp_structure(&self, other: &Self) -> Comparison {
+        if self.same_nodes(other) {
+            Comparison::Identical
+        } else if self.eq_deep(other) {
+            Comparison::Equal
+        } else {
+            Comparison::Different
+        }
+    }
+
+    // Returns true if both lists are made of exactly the same nodes.
+    fn same_nodes(&self, other: &Self) -> bool {
+        self.size == other.size &&
+            match (&self.head, &other.head) {
+                (Some(left), Some(right)) => Arc::ptr_eq(left, right),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+}
+
+impl<T: Clone + Eq> List<T> {
+    /// Compares the lists element by element, stopping as soon as both reach the same node:
+    /// since the lists have the same length, everything after it is the same too. Comparing a
+    /// list with a copy or with an update of it only looks at the elements before the shared
+    /// nodes.
+    ///
+    /// Requires `Eq`, since shared elements are assumed to be equal to themselves.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2, 3];
+    /// let updated = list.update(0, 10).unwrap();
+    ///
+    /// assert!(list.eq_fast(&list.clone()));
+    /// assert!(!list.eq_fast(&updated));
+    /// assert!(list.eq_fast(&updated.update(0, 1).unwrap()));
+    /// # }
+    /// ```
+    pub fn eq_fast(&self, other: &Self) -> bool {
+        if self.size != other.size {
+            return false;
+        }
+
+        for (left, right) in self.nodes().zip(other.nodes()) {
+            if Arc::ptr_eq(left, right) {
+                return true;
+            }
+
+            if left.data != right.data {
+                return false;
+            }
+        }
+
+        true
+    }
+}
@@ -1,26 +1,79 @@
 use std::iter::{Iterator, FromIterator, IntoIterator};
 use List;
+use instrument;
 
+use super::list;
+use super::node::{self, Node, Nodes, Link};
+
+/// An owning iterator over the elements of a `List`.
+///
+/// The iterator holds its own references to the list's nodes instead of borrowing the list, so
+/// it's `Send + 'static` whenever the elements are `Send + Sync + 'static`. A snapshot can be
+/// moved into another thread or async task and iterated there.
+///
+/// #Examples
+///
+/// ```
+/// # #[macro_use] extern crate purse;
+/// # fn main() {
+/// use purse::{IntoIter, List};
+/// use std::thread;
+///
+/// let list: List<String> = purse_list!["a".to_string(), "b".to_string()];
+/// let iter: IntoIter<String> = list.clone().into_iter();
+///
+/// let joined = thread::spawn(move || iter.collect::<Vec<_>>().join(""));
+///
+/// assert_eq!(joined.join().unwrap(), "ab");
+/// assert_eq!(list.len(), 2);
+/// # }
+/// ```
 pub struct IntoIter<T: Clone> {
-    list: List<T>,
+    link: Link<T>,
+    remaining: usize,
 }
 
 impl<T: Clone> Iterator for IntoIter<T> {
     type Item = T;
 
     fn next(&mut self) -> Option<T> {
-        let list = self.list.clone();
+        if self.remaining == 0 {
+            return None;
+        }
 
-        match list.head {
-            Some(ref link) => {
-                let node = unsafe { &*link.get() };
+        self.link.take().map(|link| {
+            self.remaining -= 1;
+            self.link = link.next().cloned();
 
-                self.list = node.next.clone();
+            instrument::clone_counted(&link.data)
+        })
+    }
 
-                Some(node.data.clone())
-            }
-            None => None,
-        }
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+/// An iterator over references to the elements of a `List`, created by `List::iter`.
+pub struct Iter<'a, T: 'a + Clone> {
+    nodes: Nodes<'a, T>,
+}
+
+impl<'a, T: 'a + Clone> Iter<'a, T> {
+    pub(super) fn new(nodes: Nodes<'a, T>) -> Self {
+        Iter { nodes }
+    }
+}
+
+impl<'a, T: 'a + Clone> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<&'a T> {
+        self.nodes.next().map(|node| &node.data)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
     }
 }
 
@@ -46,11 +99,24 @@ impl<T: Clone> FromIterator<T> for List<T> {
     /// ```
     fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
         let mut iter = iter.into_iter();
+        let mut list = match iter.next() {
+            Some(data) => List::create(data, List::empty()),
+            None => return List::empty(),
+        };
+        let mut last = list.head.clone().unwrap();
 
-        match iter.next() {
-            Some(data) => List::create(data, List::from_iter(iter)),
-            _ => List::empty(),
+        // build the list front to back, linking each new node after the previous one
+        for data in iter {
+            let link = node::new_link(Node::new(data, None)).unwrap();
+
+            last.try_link(&link);
+            last = link;
+            list.size = list::add_len(list.size, 1);
         }
+
+        list.tail = Some(last);
+
+        list
     }
 }
 
@@ -80,6 +146,9 @@ impl<T: Clone> IntoIterator for List<T> {
     /// # }
     /// ```
     fn into_iter(self) -> Self::IntoIter {
-        IntoIter { list: self }
+        IntoIter {
+            link: self.head,
+            remaining: self.size,
+        }
     }
 }
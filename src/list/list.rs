@@ -1,8 +1,12 @@
-use std::sync::Arc;
 use std::ops::Index;
+use std::mem;
+use std::ptr;
 use std::fmt;
 
-use super::node::{self, Node, Link, WeakLink};
+use instrument;
+
+use super::node::{self, Arc, Node, Nodes, Link};
+use super::iterator::Iter;
 
 /// A persistent singly linked list of elements.
 ///
@@ -33,12 +37,13 @@ use super::node::{self, Node, Link, WeakLink};
 #[derive(Clone, Default)]
 pub struct List<T: Clone> {
     pub(super) head: Link<T>,
-    pub(super) tail: WeakLink<T>,
+    pub(super) tail: Link<T>,
     pub(super) size: usize,
 }
 
 impl<T: Clone> List<T> {
-    /// Creates an empty list.
+    /// Creates an empty list. This is a `const fn`, so empty lists can be used in constants and
+    /// statics.
     ///
     /// #Examples
     ///
@@ -47,12 +52,15 @@ impl<T: Clone> List<T> {
     /// # fn main() {
     /// use purse::List;
     ///
+    /// static NOTHING: List<i32> = List::empty();
+    ///
     /// let list: List<i32> = List::empty();
     ///
     /// assert_eq!(list.len(), 0);
+    /// assert_eq!(NOTHING, list);
     /// # }
     /// ```
-    pub fn empty() -> Self {
+    pub const fn empty() -> Self {
         List {
             head: None,
             tail: None,
@@ -81,22 +89,7 @@ impl<T: Clone> List<T> {
     /// # }
     /// ```
     pub fn prepend(&self, data: T) -> Self {
-        let node = Node::new(
-            data,
-            List {
-                head: self.head.clone(),
-                tail: self.tail.clone(),
-                size: self.size,
-            },
-        );
-
-        let head = node::new_link(node);
-
-        List {
-            head: head.clone(),
-            tail: self.tail.clone(),
-            size: 1 + self.size,
-        }
+        List::create(data, self.clone())
     }
 
     /// Creates a list that starts with the original list and ends with the given element
@@ -118,12 +111,18 @@ impl<T: Clone> List<T> {
     /// assert_eq!(appended, purse_list![1, 2, 3]);
     /// # }
     /// ```
+    #[track_caller]
     pub fn append(self, data: T) -> Self {
         self.concat(&List::create(data, List::empty()))
     }
 
     /// Creates a list from an item and the tail list.
     ///
+    /// #Panics
+    ///
+    /// Panics if the length of the new list overflows `usize`. Only lists rebuilt from wrong raw
+    /// parts can be that long.
+    ///
     /// #Examples
     ///
     /// ```
@@ -138,20 +137,32 @@ impl<T: Clone> List<T> {
     /// # }
     /// ```
     pub fn create(data: T, rest: Self) -> Self {
-        let tail = rest.tail.clone();
-        let size = 1 + rest.size;
-        let head = node::new_link(Node::new(data, rest));
+        let size = add_len(1, rest.size);
+        let head = node::new_link(Node::new(data, rest.head));
+        let tail = rest.tail;
 
         List {
             head: head.clone(),
-            tail: tail.or(head.as_ref().map(Arc::downgrade)),
-            size: size,
+            tail: tail.or(head),
+            size,
         }
     }
 
     /// Creates a new list with the elements of the first list followed by the elements of the
     /// second.
     ///
+    /// The right list is linked after the last node of this one in O(1) when possible. This list
+    /// is copied instead if something is already linked after its last node, or if linking could
+    /// make the nodes loop back on themselves: when both lists end at the same node, or when the
+    /// right list's last node is already followed by other nodes. This also holds when other
+    /// threads concatenate the same lists at the same time, e.g. `a.concat(&b)` on one thread
+    /// and `b.concat(&a)` on another: at least one of them copies. In debug builds, every
+    /// in-place link is also checked not to have created a loop.
+    ///
+    /// #Panics
+    ///
+    /// Panics if the length of the new list overflows `usize`, before linking any nodes.
+    ///
     /// #Examples
     ///
     /// ```
@@ -174,65 +185,445 @@ impl<T: Clone> List<T> {
     /// assert_eq!(empty.clone().concat(&empty).len(), 0);
     /// # }
     /// ```
+    #[track_caller]
     pub fn concat(self, right: &Self) -> Self {
-        let do_immut = || List::concat_immut(&self.head, right);
+        // if the right list is empty, return this same list
+        if right.size == 0 {
+            return self;
+        }
+
+        if self.head.is_none() {
+            return right.clone();
+        }
+
+        let left_tail = self.tail.clone();
+        let mut list = self;
+
+        if !list.concat_mut(right) {
+            return List::concat_immut(&list, right);
+        }
+
+        // concat_mut never links a loop, but following the links is cheap enough to make sure
+        // in debug builds
+        debug_assert!(
+            !left_tail.is_some_and(|tail| node::reaches(&right.head, &tail)),
+            "concat linked a list into itself"
+        );
+
+        list
+    }
 
-        self.head.as_ref().map_or(right.clone(), |link| {
-            // if the right list is empty, return this same list cloned
-            if right.size == 0 {
-                return self.clone();
+    // Copies the nodes of the left list and links the last copy to the head of the right list.
+    #[track_caller]
+    pub(super) fn concat_immut(left: &Self, right: &Self) -> Self {
+        let data: Vec<&T> = left.nodes().map(|node| &node.data).collect();
+
+        instrument::deep_copied("concat", data.len(), add_len(data.len(), right.size));
+
+        data.into_iter().rev().fold(right.clone(), |list, data| {
+            List::create(instrument::clone_counted(data), list)
+        })
+    }
+
+    // Add the elements of a list to an existing list by linking its head after the last node.
+    // Returns false without changing anything if another list has already claimed that node, or
+    // if linking could make the nodes loop back on themselves.
+    pub(super) fn concat_mut(&mut self, right: &Self) -> bool {
+        let size = add_len(self.size, right.size);
+
+        if let (Some(tail), Some(head)) = (&self.tail, &right.head) {
+            if !tail.try_claim() {
+                return false;
             }
 
-            // If another list has this link as its head, concat immutably
-            if Arc::strong_count(link) != 1 {
-                return do_immut();
+            // The right list's nodes only lead back to our last node if they continue past its
+            // end, i.e. if its last node is claimed too. That includes both lists ending at the
+            // same node, which we've just claimed. Since we claim before checking, of two lists
+            // being concatenated onto each other at the same time, at least one sees the other's
+            // claim and backs off.
+            if right.tail.as_ref().is_some_and(|right_tail| right_tail.is_claimed()) {
+                tail.release();
+
+                return false;
             }
 
-            let node = node::get_unwrapped_link_node_mut(link);
+            tail.link(head);
+        }
 
-            // if another list is modifying this link, concat immutably
-            if node.try_mutate() {
-                let mut list = self.clone();
+        if self.head.is_none() {
+            self.head = right.head.clone();
+        }
+
+        if right.tail.is_some() {
+            self.tail = right.tail.clone();
+        }
 
-                list.concat_mut(right);
-                node.end_mutate();
+        self.size = size;
 
-                list
-            } else {
-                do_immut()
-            }
+        true
+    }
+
+    pub(super) fn nodes(&self) -> Nodes<'_, T> {
+        Nodes::new(&self.head, self.size)
+    }
+
+    // Returns the list of elements from the given index onwards, sharing its nodes with this one.
+    pub(crate) fn suffix(&self, index: usize) -> Self {
+        if index >= self.size {
+            return List::empty();
+        }
+
+        List {
+            head: self.nodes().nth(index).cloned(),
+            tail: self.tail.clone(),
+            size: self.size - index,
+        }
+    }
+
+    /// Creates a new list of the first `n` elements, or of all of them if the list is shorter.
+    /// The elements are copied, so the new list doesn't keep the rest of this one alive.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2, 3];
+    ///
+    /// assert_eq!(list.take(2), purse_list![1, 2]);
+    /// assert_eq!(list.take(5), list);
+    /// # }
+    /// ```
+    pub fn take(&self, n: usize) -> Self {
+        self.iter().take(n).map(instrument::clone_counted).collect()
+    }
+
+    /// Returns the list without its first `n` elements, sharing its nodes with this one. Takes
+    /// O(n) time to walk to the new head and copies nothing. Skipping at least as many elements
+    /// as there are gives an empty list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// let list = purse_list![1, 2, 3];
+    ///
+    /// assert_eq!(list.skip(1), purse_list![2, 3]);
+    /// assert_eq!(list.skip(1).len(), 2);
+    /// assert_eq!(list.skip(3), List::empty());
+    /// # }
+    /// ```
+    pub fn skip(&self, n: usize) -> Self {
+        self.suffix(n)
+    }
+
+    /// Returns the first `n` elements and the rest of the list as two lists. Only the prefix is
+    /// copied; the rest shares its nodes with this list. If `n` is not less than the length, the
+    /// second list is empty.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// let list = purse_list![1, 2, 3, 4];
+    ///
+    /// assert_eq!(list.split_at(1), (purse_list![1], purse_list![2, 3, 4]));
+    /// assert_eq!(list.split_at(4), (list.clone(), List::empty()));
+    /// # }
+    /// ```
+    pub fn split_at(&self, n: usize) -> (Self, Self) {
+        (self.take(n), self.skip(n))
+    }
+
+    /// Creates a new list of the elements before the first one for which `pred` returns false.
+    /// The elements are copied.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2, 5, 1];
+    ///
+    /// assert_eq!(list.take_while(|&x| x < 3), purse_list![1, 2]);
+    /// # }
+    /// ```
+    pub fn take_while<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Self {
+        self.iter().take_while(|&data| pred(data)).map(instrument::clone_counted).collect()
+    }
+
+    /// Returns the list from the first element for which `pred` returns false onwards, sharing
+    /// its nodes with this list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2, 5, 1];
+    ///
+    /// assert_eq!(list.drop_while(|&x| x < 3), purse_list![5, 1]);
+    /// # }
+    /// ```
+    pub fn drop_while<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Self {
+        self.skip(self.iter().take_while(|&data| pred(data)).count())
+    }
+
+    /// Splits the list before the first element for which `pred` returns false, the same as
+    /// `take_while` and `drop_while` together but calling `pred` only once per element.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2, 5, 1];
+    ///
+    /// assert_eq!(list.span(|&x| x < 3), (purse_list![1, 2], purse_list![5, 1]));
+    /// # }
+    /// ```
+    pub fn span<F: FnMut(&T) -> bool>(&self, mut pred: F) -> (Self, Self) {
+        self.split_at(self.iter().take_while(|&data| pred(data)).count())
+    }
+
+    /// Returns a reference to the first element for which `pred` returns true, or None if there
+    /// isn't one.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 4, 6];
+    ///
+    /// assert_eq!(list.find(|&x| x % 2 == 0), Some(&4));
+    /// assert_eq!(list.find(|&x| x > 10), None);
+    /// # }
+    /// ```
+    pub fn find<F: FnMut(&T) -> bool>(&self, mut pred: F) -> Option<&T> {
+        self.iter().find(|&data| pred(data))
+    }
+
+    /// Returns the index of the first element for which `pred` returns true, or None if there
+    /// isn't one.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 4, 6];
+    ///
+    /// assert_eq!(list.position(|&x| x % 2 == 0), Some(1));
+    /// assert_eq!(list.position(|&x| x > 10), None);
+    /// # }
+    /// ```
+    pub fn position<F: FnMut(&T) -> bool>(&self, pred: F) -> Option<usize> {
+        self.iter().position(pred)
+    }
+
+    /// Creates a list of pairs of the elements of both lists at the same positions. The new list
+    /// is as long as the shorter of the two.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let numbers = purse_list![1, 2, 3];
+    /// let letters = purse_list!['a', 'b'];
+    ///
+    /// assert_eq!(numbers.zip(&letters), purse_list![(1, 'a'), (2, 'b')]);
+    /// # }
+    /// ```
+    pub fn zip<U: Clone>(&self, other: &List<U>) -> List<(T, U)> {
+        self.zip_with(other, |left, right| {
+            (instrument::clone_counted(left), instrument::clone_counted(right))
         })
     }
 
-    pub(super) fn concat_immut(link: &Link<T>, right: &Self) -> Self {
-        let node = node::get_unwrapped_link_node(link.as_ref().unwrap());
+    /// Creates a list of the results of calling `f` on the elements of both lists at the same
+    /// positions. The new list is as long as the shorter of the two.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let prices = purse_list![10, 20, 30];
+    /// let counts = purse_list![2, 1, 0, 5];
+    ///
+    /// assert_eq!(prices.zip_with(&counts, |p, c| p * c), purse_list![20, 20, 0]);
+    /// # }
+    /// ```
+    pub fn zip_with<U, V, F>(&self, other: &List<U>, mut f: F) -> List<V>
+    where
+        U: Clone,
+        V: Clone,
+        F: FnMut(&T, &U) -> V,
+    {
+        self.iter().zip(other.iter()).map(|(left, right)| f(left, right)).collect()
+    }
 
-        node.concat_list(link, &right)
+    /// Creates a list of the pairs of adjacent elements: the first and second, the second and
+    /// third, and so on. Lists with fewer than two elements give an empty list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2, 3];
+    ///
+    /// assert_eq!(list.pairwise(), purse_list![(1, 2), (2, 3)]);
+    /// assert_eq!(purse_list![1].pairwise().len(), 0);
+    /// # }
+    /// ```
+    pub fn pairwise(&self) -> List<(T, T)> {
+        self.zip(&self.skip(1))
     }
 
-    // Add the elements of a list to an existing list by mutating its fields recursively.
-    pub(super) fn concat_mut(&mut self, right: &Self) {
-        let head = match self.head.clone() {
-            Some(link) => {
-                node::get_unwrapped_link_node_mut(&link).next.concat_mut(
-                    right,
-                );
+    /// Creates a list of the results of calling `f` on each pair of adjacent elements, in order.
+    /// Lists with fewer than two elements give an empty list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let series = purse_list![1, 4, 9, 16];
+    ///
+    /// assert_eq!(series.adjacent_map(|prev, next| next - prev), purse_list![3, 5, 7]);
+    /// # }
+    /// ```
+    pub fn adjacent_map<U: Clone, F: FnMut(&T, &T) -> U>(&self, f: F) -> List<U> {
+        self.zip_with(&self.skip(1), f)
+    }
 
-                Some(link)
-            }
-            None => right.head.clone(),
+    /// Splits the list in two at the given index. The list keeps the elements before `at` and the
+    /// elements from `at` onwards are returned, sharing their nodes with the original.
+    ///
+    /// No elements are copied. If no other list shares the nodes before `at`, the link to the
+    /// returned list is cut, so this list no longer keeps it alive and can be appended to in
+    /// place again. If `at` is not less than the length, the list is left unchanged and an empty
+    /// list is returned.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let mut list = purse_list![1, 2, 3, 4];
+    /// let rest = list.split_off(1);
+    ///
+    /// assert_eq!(list, purse_list![1]);
+    /// assert_eq!(rest, purse_list![2, 3, 4]);
+    ///
+    /// let mut shared = rest.clone();
+    ///
+    /// assert_eq!(shared.split_off(2), purse_list![4]);
+    /// assert_eq!(shared, purse_list![2, 3]);
+    /// assert_eq!(rest, purse_list![2, 3, 4]);
+    /// # }
+    /// ```
+    pub fn split_off(&mut self, at: usize) -> Self {
+        if at >= self.size {
+            return List::empty();
+        }
+
+        if at == 0 {
+            return mem::replace(self, List::empty());
+        }
+
+        let suffix = self.suffix(at);
+
+        // cut the link after the last node of the prefix if every node up to it is ours alone
+        let mut link = self.head.as_mut();
+
+        for _ in 1..at {
+            link = link.and_then(Arc::get_mut).and_then(Node::next_mut);
+        }
+
+        if let Some(node) = link.and_then(Arc::get_mut) {
+            node.unlink();
+        }
+
+        self.tail = self.nodes().nth(at - 1).cloned();
+        self.size = at;
+
+        suffix
+    }
+
+    /// Consumes the list, returning opaque pointers to its first and last nodes along with its
+    /// length. The pointers can be passed through FFI or stored elsewhere, but the only thing they
+    /// can be used for is rebuilding the list with `from_raw_parts`; until then, the nodes are
+    /// kept alive. Both pointers are null for an empty list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// let list = purse_list![1, 2, 3];
+    /// let (head, tail, len) = list.clone().into_raw_parts();
+    ///
+    /// let rebuilt = unsafe { List::<i32>::from_raw_parts(head, tail, len) };
+    ///
+    /// assert_eq!(rebuilt, list);
+    /// # }
+    /// ```
+    pub fn into_raw_parts(self) -> (*const (), *const (), usize) {
+        let into_raw = |link: Link<T>| {
+            link.map_or(ptr::null(), |link| Arc::into_raw(link) as *const ())
         };
 
-        if let Some(ref link) = self.tail {
-            let tail = link.upgrade().unwrap();
-            let tail_node = node::get_unwrapped_link_node_mut(&tail);
+        (into_raw(self.head), into_raw(self.tail), self.size)
+    }
 
-            tail_node.next = right.clone();
+    /// Rebuilds a list from the parts returned by `into_raw_parts`.
+    ///
+    /// # Safety
+    ///
+    /// The parts must all come from the same call to `into_raw_parts` on a list with the same
+    /// element type, and each set of parts may only be used to rebuild a list once.
+    pub unsafe fn from_raw_parts(head: *const (), tail: *const (), len: usize) -> Self {
+        let from_raw = |ptr: *const ()| if ptr.is_null() {
+            None
+        } else {
+            Some(Arc::from_raw(ptr as *const Node<T>))
         };
 
-        self.head = head.or(right.head.clone());
-        self.tail = right.tail.clone();
-        self.size += right.size;
+        List {
+            head: from_raw(head),
+            tail: from_raw(tail),
+            size: len,
+        }
+    }
+
+    /// Returns an iterator over references to the elements of the list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![String::from("a"), String::from("b")];
+    /// let lengths: Vec<usize> = list.iter().map(String::len).collect();
+    ///
+    /// assert_eq!(lengths, vec![1, 1]);
+    /// assert_eq!(list.iter().last(), Some(&String::from("b")));
+    /// # }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter::new(self.nodes())
     }
 
     /// Rerives the length of a list.
@@ -255,10 +646,21 @@ impl<T: Clone> List<T> {
         self.size
     }
 
-    fn get_link_data(link: &Link<T>) -> Option<&T> {
-        link.as_ref().map(|link_cell| {
-            &node::get_unwrapped_link_node(&link_cell).data
-        })
+    /// Returns true if the list has no elements.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// assert!(List::<i32>::empty().is_empty());
+    /// assert!(!purse_list![1].is_empty());
+    /// # }
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
     }
 
     /// Returns a reference to the first element of the list or None if it's empty.
@@ -278,7 +680,7 @@ impl<T: Clone> List<T> {
     /// # }
     /// ```
     pub fn first(&self) -> Option<&T> {
-        List::get_link_data(&self.head)
+        self.head.as_ref().map(|link| &link.data)
     }
 
     /// Returns a reference to the last element of the list or None if it's empty.
@@ -298,14 +700,432 @@ impl<T: Clone> List<T> {
     /// # }
     /// ```
     pub fn last(&self) -> Option<&T> {
-        self.tail.as_ref().map(|weak| unsafe {
-            &(*weak.upgrade().unwrap().get()).data
+        self.tail.as_ref().map(|link| &link.data)
+    }
+
+    /// Returns a reference to the element at the given index, or None if the index is out of
+    /// bounds.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list!['a', 'b', 'c'];
+    ///
+    /// assert_eq!(list.get(1), Some(&'b'));
+    /// assert_eq!(list.get(3), None);
+    /// # }
+    /// ```
+    pub fn get(&self, index: usize) -> Option<&T> {
+        self.nodes().nth(index).map(|node| &node.data)
+    }
+
+    /// Creates a new list with the element at the given index replaced. Only the elements before
+    /// the index are copied; the ones after it are shared with this list. Returns None if the
+    /// index is out of bounds.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2, 3];
+    ///
+    /// assert_eq!(list.update(1, 20), Some(purse_list![1, 20, 3]));
+    /// assert_eq!(list.update(3, 40), None);
+    /// assert_eq!(list, purse_list![1, 2, 3]);
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn update(&self, index: usize, data: T) -> Option<Self> {
+        if index >= self.size {
+            return None;
+        }
+
+        Some(self.with_prefix("update", index, Some(data), index + 1))
+    }
+
+    /// Creates a new list with the given element inserted at the index, shifting the elements
+    /// after it. The elements from the index onwards are shared with this list. Returns None if
+    /// the index is greater than the length.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 3];
+    ///
+    /// assert_eq!(list.insert(1, 2), Some(purse_list![1, 2, 3]));
+    /// assert_eq!(list.insert(2, 4), Some(purse_list![1, 3, 4]));
+    /// assert_eq!(list.insert(3, 4), None);
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn insert(&self, index: usize, data: T) -> Option<Self> {
+        if index > self.size {
+            return None;
+        }
+
+        Some(self.with_prefix("insert", index, Some(data), index))
+    }
+
+    /// Creates a new list without the element at the given index. The elements after it are
+    /// shared with this list. Returns None if the index is out of bounds.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list!['a', 'b', 'c'];
+    ///
+    /// assert_eq!(list.remove(1), Some(purse_list!['a', 'c']));
+    /// assert_eq!(list.remove(3), None);
+    /// assert_eq!(list, purse_list!['a', 'b', 'c']);
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn remove(&self, index: usize) -> Option<Self> {
+        if index >= self.size {
+            return None;
+        }
+
+        Some(self.with_prefix("remove", index, None, index + 1))
+    }
+
+    /// Creates a new list with the elements of this one in reverse order. Each element is prepended
+    /// to the result as the list is walked from the front, so reversing takes a single pass.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// let list = purse_list![1, 2, 3];
+    ///
+    /// assert_eq!(list.reverse(), purse_list![3, 2, 1]);
+    /// assert_eq!(list.reverse().last(), Some(&1));
+    ///
+    /// let long: List<u32> = (0..1_000_000).collect();
+    ///
+    /// assert_eq!(long.reverse().first(), Some(&999_999));
+    /// # }
+    /// ```
+    pub fn reverse(&self) -> Self {
+        self.iter().fold(List::empty(), |list, data| {
+            List::create(instrument::clone_counted(data), list)
         })
     }
+
+    /// Creates a new list by calling `f` on every element in order. Each result is appended to the
+    /// new list as soon as it's computed, so `f` sees the elements front to back.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2, 3];
+    ///
+    /// assert_eq!(list.map(|x| x * 10), purse_list![10, 20, 30]);
+    /// assert_eq!(list.map(|&x| x > 1), purse_list![false, true, true]);
+    /// # }
+    /// ```
+    pub fn map<U: Clone, F: FnMut(&T) -> U>(&self, f: F) -> List<U> {
+        self.iter().map(f).collect()
+    }
+
+    /// Creates a new list with the elements for which `pred` returns true. The elements after the
+    /// last one that's left out are shared with this list instead of being copied.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2, 3, 4, 5, 6];
+    ///
+    /// assert_eq!(list.filter(|&x| x % 2 == 0), purse_list![2, 4, 6]);
+    /// assert_eq!(list.filter(|&x| x != 2), purse_list![1, 3, 4, 5, 6]);
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn filter<F: FnMut(&T) -> bool>(&self, pred: F) -> Self {
+        let keep: Vec<bool> = self.iter().map(pred).collect();
+
+        self.select("filter", &keep)
+    }
+
+    /// Creates a new list of the values returned by `f` for the elements where it returns
+    /// `Some`. Since the new elements can be of another type, no nodes are shared with this list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list!["1", "two", "3"];
+    ///
+    /// assert_eq!(list.filter_map(|s| s.parse().ok()), purse_list![1, 3]);
+    /// # }
+    /// ```
+    pub fn filter_map<U: Clone, F: FnMut(&T) -> Option<U>>(&self, f: F) -> List<U> {
+        self.iter().filter_map(f).collect()
+    }
+
+    /// Combines the elements from first to last into a single value, starting from `init`.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list!["a", "b", "c"];
+    ///
+    /// assert_eq!(list.fold(String::new(), |acc, s| acc + s), "abc");
+    /// assert_eq!(list.fold(0, |count, _| count + 1), 3);
+    /// # }
+    /// ```
+    pub fn fold<B, F: FnMut(B, &T) -> B>(&self, init: B, f: F) -> B {
+        self.iter().fold(init, f)
+    }
+
+    /// Combines the elements from last to first into a single value, starting from `init`. The
+    /// elements are first collected into a `Vec` of references and folded from its end, which
+    /// costs a pointer per element on the heap instead of a stack frame per element.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// let list = purse_list!["a", "b", "c"];
+    ///
+    /// assert_eq!(list.fold_right(String::new(), |s, acc| acc + s), "cba");
+    ///
+    /// let long: List<u64> = (0..1_000_000).collect();
+    /// let rebuilt = long.fold_right(List::empty(), |&x, rest| rest.prepend(x));
+    ///
+    /// assert_eq!(rebuilt, long);
+    /// # }
+    /// ```
+    pub fn fold_right<B, F: FnMut(&T, B) -> B>(&self, init: B, mut f: F) -> B {
+        let stack: Vec<&T> = self.iter().collect();
+
+        stack.into_iter().rev().fold(init, |acc, data| f(data, acc))
+    }
+
+    // Creates a list of the elements whose flag in `keep` is true. The elements after the last one
+    // that isn't kept are shared; copying the ones before it is reported as `operation`.
+    #[track_caller]
+    pub(super) fn select(&self, operation: &'static str, keep: &[bool]) -> Self {
+        let dropped = match keep.iter().rposition(|&kept| !kept) {
+            Some(index) => index,
+            None => return self.clone(),
+        };
+
+        let prefix: List<T> = self.iter()
+            .zip(keep)
+            .take(dropped)
+            .filter(|&(_, &kept)| kept)
+            .map(|(data, _)| instrument::clone_counted(data))
+            .collect();
+        let suffix = self.suffix(dropped + 1);
+
+        if prefix.size > 0 {
+            instrument::deep_copied(operation, prefix.size, prefix.size + suffix.size);
+        }
+
+        prefix.concat(&suffix)
+    }
+
+    // Creates a list of the first `len` elements of this one and an optional new element, followed
+    // by the elements from `rest` onwards, which are shared. The new prefix has an unclaimed tail,
+    // so linking the rest to it never copies. Copying the first `len` elements is reported as
+    // `operation`.
+    #[track_caller]
+    fn with_prefix(
+        &self,
+        operation: &'static str,
+        len: usize,
+        data: Option<T>,
+        rest: usize,
+    ) -> Self {
+        let prefix: List<T> = self.iter()
+            .take(len)
+            .map(instrument::clone_counted)
+            .chain(data)
+            .collect();
+        let suffix = self.suffix(rest);
+
+        if len > 0 {
+            instrument::deep_copied(operation, len, prefix.size + suffix.size);
+        }
+
+        prefix.concat(&suffix)
+    }
+
+    /// Creates a list that starts with the given element and continues with this one. The same as
+    /// `prepend`.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![2, 3];
+    ///
+    /// assert_eq!(list.cons(1), purse_list![1, 2, 3]);
+    /// # }
+    /// ```
+    pub fn cons(&self, data: T) -> Self {
+        self.prepend(data)
+    }
+
+    /// Returns a reference to the first element of the list or None if it's empty. The same as
+    /// `first`.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// assert_eq!(purse_list![1, 2].head(), Some(&1));
+    /// assert_eq!(List::<i32>::empty().head(), None);
+    /// # }
+    /// ```
+    pub fn head(&self) -> Option<&T> {
+        self.first()
+    }
+
+    /// Returns the list of all elements after the first one in O(1), sharing its nodes with this
+    /// list. The tail of an empty list is empty.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// let list = purse_list![1, 2, 3];
+    ///
+    /// assert_eq!(list.tail(), purse_list![2, 3]);
+    /// assert_eq!(list.tail().tail().tail(), List::empty());
+    /// assert_eq!(List::<i32>::empty().tail(), List::empty());
+    /// # }
+    /// ```
+    pub fn tail(&self) -> Self {
+        self.suffix(1)
+    }
+
+    /// Returns the first element together with the rest of the list, or None if the list is
+    /// empty. The rest shares its nodes with this list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// fn sum(list: &List<i32>) -> i32 {
+    ///     match list.uncons() {
+    ///         Some((head, rest)) => head + sum(&rest),
+    ///         None => 0,
+    ///     }
+    /// }
+    ///
+    /// assert_eq!(sum(&purse_list![1, 2, 3]), 6);
+    /// assert_eq!(purse_list!['a'].uncons(), Some((&'a', List::empty())));
+    /// # }
+    /// ```
+    pub fn uncons(&self) -> Option<(&T, Self)> {
+        self.first().map(|head| (head, self.tail()))
+    }
+
+    /// Creates a list that starts with this one and ends with the given element. Unlike `append`,
+    /// this doesn't consume the list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2];
+    ///
+    /// assert_eq!(list.snoc(3), purse_list![1, 2, 3]);
+    /// assert_eq!(list, purse_list![1, 2]);
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn snoc(&self, data: T) -> Self {
+        self.clone().append(data)
+    }
+}
+
+impl<T: Clone + PartialEq> List<T> {
+    /// Returns true if the list has an element equal to the given one.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list!["a", "b"];
+    ///
+    /// assert!(list.contains(&"b"));
+    /// assert!(!list.contains(&"c"));
+    /// # }
+    /// ```
+    pub fn contains(&self, data: &T) -> bool {
+        self.iter().any(|elem| elem == data)
+    }
+}
+
+impl<A: Clone, B: Clone> List<(A, B)> {
+    /// Splits a list of pairs into a list of the first elements and a list of the second ones,
+    /// walking the list once.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let pairs = purse_list![(1, 'a'), (2, 'b')];
+    ///
+    /// assert_eq!(pairs.unzip(), (purse_list![1, 2], purse_list!['a', 'b']));
+    /// assert_eq!(purse_list![1, 2].zip(&purse_list!['a', 'b']), pairs);
+    /// # }
+    /// ```
+    pub fn unzip(&self) -> (List<A>, List<B>) {
+        let (left, right): (Vec<A>, Vec<B>) = self.iter().map(instrument::clone_counted).unzip();
+
+        (left.into_iter().collect(), right.into_iter().collect())
+    }
+}
+
+// Adds two list lengths. Traversals trust the length of a list, so one that wrapped around would
+// silently hide elements; panic instead.
+pub(super) fn add_len(left: usize, right: usize) -> usize {
+    left.checked_add(right).expect("list length overflow")
 }
 
 /// Elements of a list may be accessed by index.
 ///
+/// #Panics
+///
+/// Panics if the index is not less than the length of the list. Use `get` to handle missing
+/// indices instead.
+///
 /// #Examples
 ///
 /// ```
@@ -321,15 +1141,13 @@ impl<T: Clone> Index<usize> for List<T> {
     type Output = T;
 
     fn index(&self, index: usize) -> &T {
-        if index >= self.size {
+        self.get(index).unwrap_or_else(|| {
             panic!(
                 "index out of bounds: the len is {} but the index is {}",
                 self.size,
                 index
-            );
-        }
-
-        &node::get_link_node(&self.head).index(index)
+            )
+        })
     }
 }
 
@@ -360,17 +1178,9 @@ where
             return false;
         };
 
-        match (&self.head, &other.head) {
-            // both empty
-            (&None, &None) => true,
-            (&Some(ref self_head), &Some(ref other_head)) => {
-                let self_head = node::get_unwrapped_link_node(&self_head);
-                let other_head = node::get_unwrapped_link_node(&other_head);
-
-                self_head.data == other_head.data && self_head.next == other_head.next
-            }
-            _ => false,
-        }
+        self.nodes().zip(other.nodes()).all(|(self_node, other_node)| {
+            self_node.data == other_node.data
+        })
     }
 }
 
@@ -382,9 +1192,9 @@ where
 
 impl<T: Clone + fmt::Debug> fmt::Debug for List<T> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.head.as_ref().map_or(write!(f, "[]"), |ref link| {
-            write!(f, "[{:?}]", node::get_unwrapped_link_node(&link))
-        })
+        f.debug_list()
+            .entries(self.nodes().map(|node| &node.data))
+            .finish()
     }
 }
 
@@ -410,6 +1220,45 @@ macro_rules! purse_list {
     };
 
     [ $head:expr, $($rest:expr),* ] => {
-        $crate::List::create($head, purse_list![$($rest),*])
+        $crate::List::create($head, $crate::purse_list![$($rest),*])
+    };
+}
+
+/// Macro for defining a global list that is built the first time it's accessed
+///
+/// # Example
+///
+/// ```
+/// # #[macro_use] extern crate purse;
+/// use purse::List;
+///
+/// static_purse_list! {
+///     static PRIMES: List<u32> = [2, 3, 5, 7];
+/// }
+///
+/// # fn main() {
+/// assert_eq!(PRIMES.len(), 4);
+/// assert_eq!(*PRIMES, purse_list![2, 3, 5, 7]);
+/// # }
+/// ```
+///
+/// The expansion doesn't depend on `purse_list!` being in scope:
+///
+/// ```edition2021
+/// use purse::{static_purse_list, List};
+///
+/// static_purse_list! {
+///     static EVENS: List<u32> = [0, 2, 4];
+/// }
+///
+/// assert_eq!(EVENS.len(), 3);
+/// assert_eq!(EVENS.last(), Some(&4));
+/// ```
+#[macro_export]
+macro_rules! static_purse_list {
+    ( $(#[$attr:meta])* $vis:vis static $name:ident : $ty:ty = [ $($elem:expr),* ]; ) => {
+        $(#[$attr])*
+        $vis static $name: ::std::sync::LazyLock<$ty> =
+            ::std::sync::LazyLock::new(|| $crate::purse_list![$($elem),*]);
     };
 }
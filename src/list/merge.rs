@@ -0,0 +1,182 @@
+use List;
+use instrument;
+
+/// A region that was changed differently on both sides of a three-way merge.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Conflict<T: Clone> {
+    /// The position in the merged list where the conflicting region would go.
+    pub index: usize,
+    /// The elements of the region in the base version.
+    pub base: List<T>,
+    /// The elements of the region in the left version.
+    pub left: List<T>,
+    /// The elements of the region in the right version.
+    pub right: List<T>,
+}
+
+impl<T: Clone + PartialEq> List<T> {
+    /// Merges two versions of a list that were both derived from `base`. Changes made on only
+    /// one side are applied; regions changed differently on both sides are returned as conflicts.
+    ///
+    /// Elements are matched against the base by longest common subsequence, which takes time and
+    /// memory proportional to the product of the lengths of the parts that differ. If one side is
+    /// unchanged, the other is returned as is, sharing all of its nodes.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// let base = purse_list!['a', 'b', 'c'];
+    /// let left = purse_list!['a', 'x', 'b', 'c'];
+    /// let right = purse_list!['a', 'b'];
+    ///
+    /// assert_eq!(List::merge(&base, &left, &right), Ok(purse_list!['a', 'x', 'b']));
+    ///
+    /// let conflicting = purse_list!['a', 'y', 'b'];
+    /// let conflicts = List::merge(&base, &left, &conflicting).unwrap_err();
+    ///
+    /// assert_eq!(conflicts.len(), 1);
+    /// assert_eq!(conflicts[0].index, 1);
+    /// assert_eq!(conflicts[0].left, purse_list!['x']);
+    /// assert_eq!(conflicts[0].right, purse_list!['y']);
+    /// # }
+    /// ```
+    pub fn merge(base: &Self, left: &Self, right: &Self) -> Result<Self, Vec<Conflict<T>>> {
+        if left == base || left == right {
+            return Ok(right.clone());
+        }
+
+        if right == base {
+            return Ok(left.clone());
+        }
+
+        let base: Vec<&T> = base.iter().collect();
+        let left: Vec<&T> = left.iter().collect();
+        let right: Vec<&T> = right.iter().collect();
+
+        let to_left = match_common(&base, &left);
+        let to_right = match_common(&base, &right);
+
+        let mut merged: Vec<&T> = Vec::new();
+        let mut conflicts = Vec::new();
+        let (mut i, mut j, mut k) = (0, 0, 0);
+
+        loop {
+            // a run of base elements that are kept, in place, on both sides
+            let mut stable = 0;
+
+            while i + stable < base.len() && to_left[i + stable] == Some(j + stable) &&
+                to_right[i + stable] == Some(k + stable)
+            {
+                stable += 1;
+            }
+
+            if stable > 0 {
+                merged.extend_from_slice(&base[i..i + stable]);
+                i += stable;
+                j += stable;
+                k += stable;
+
+                continue;
+            }
+
+            // the next base element kept on both sides ends the changed region
+            let sync = (i..base.len()).find(|&o| to_left[o].is_some() && to_right[o].is_some());
+            let (o, next_j, next_k) = match sync {
+                Some(o) => (o, to_left[o].unwrap(), to_right[o].unwrap()),
+                None => (base.len(), left.len(), right.len()),
+            };
+
+            let base_part = &base[i..o];
+            let left_part = &left[j..next_j];
+            let right_part = &right[k..next_k];
+
+            if left_part == base_part {
+                merged.extend_from_slice(right_part);
+            } else if right_part == base_part || left_part == right_part {
+                merged.extend_from_slice(left_part);
+            } else {
+                conflicts.push(Conflict {
+                    index: merged.len(),
+                    base: base_part.iter().map(|&data| instrument::clone_counted(data)).collect(),
+                    left: left_part.iter().map(|&data| instrument::clone_counted(data)).collect(),
+                    right: right_part.iter().map(|&data| instrument::clone_counted(data)).collect(),
+                });
+            }
+
+            if sync.is_none() {
+                break;
+            }
+
+            i = o;
+            j = next_j;
+            k = next_k;
+        }
+
+        if conflicts.is_empty() {
+            Ok(merged.into_iter().map(instrument::clone_counted).collect())
+        } else {
+            Err(conflicts)
+        }
+    }
+}
+
+// Matches the elements of `a` to elements of `b` along a longest common subsequence. Returns, for
+// every index of `a`, the index of the matching element of `b` if there is one.
+fn match_common<T: PartialEq>(a: &[&T], b: &[&T]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; a.len()];
+
+    // elements shared at the start and end are matched directly
+    let prefix = a.iter().zip(b).take_while(|&(x, y)| x == y).count();
+    let suffix = a[prefix..]
+        .iter()
+        .rev()
+        .zip(b[prefix..].iter().rev())
+        .take_while(|&(x, y)| x == y)
+        .count();
+
+    for (i, m) in matches.iter_mut().enumerate().take(prefix) {
+        *m = Some(i);
+    }
+
+    for offset in 1..suffix + 1 {
+        matches[a.len() - offset] = Some(b.len() - offset);
+    }
+
+    let a_mid = &a[prefix..a.len() - suffix];
+    let b_mid = &b[prefix..b.len() - suffix];
+    let width = b_mid.len() + 1;
+
+    // lengths[i * width + j] is the length of the longest common subsequence of a_mid[i..] and
+    // b_mid[j..]
+    let mut lengths = vec![0; (a_mid.len() + 1) * width];
+
+    for i in (0..a_mid.len()).rev() {
+        for j in (0..b_mid.len()).rev() {
+            lengths[i * width + j] = if a_mid[i] == b_mid[j] {
+                lengths[(i + 1) * width + j + 1] + 1
+            } else {
+                lengths[(i + 1) * width + j].max(lengths[i * width + j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+
+    while i < a_mid.len() && j < b_mid.len() {
+        if a_mid[i] == b_mid[j] {
+            matches[prefix + i] = Some(prefix + j);
+            i += 1;
+            j += 1;
+        } else if lengths[(i + 1) * width + j] >= lengths[i * width + j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    matches
+}
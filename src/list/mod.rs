@@ -1,7 +1,18 @@
 #[macro_use]
+#[allow(clippy::module_inception)]
 pub mod list;
+pub mod compare;
 pub mod iterator;
+pub mod merge;
 mod node;
+mod shared;
+mod sort;
+pub mod unique;
+#[cfg(feature = "rand")]
+mod random;
+pub mod walker;
+#[cfg(feature = "schemars")]
+mod schema;
 
 #[test]
 #[should_panic(expected = "index out of bounds: the len is 0 but the index is 0")]
@@ -14,12 +25,12 @@ fn out_of_bounds_index() {
 
 #[test]
 fn concat_mut() {
-    use std::sync::Arc;
+    use list::node::Arc;
 
     let mut list1 = purse_list!['a', 'b'];
     let list2 = purse_list!['c', 'd'];
 
-    assert_eq!(Arc::strong_count(&list1.head.as_ref().unwrap()), 1);
+    assert_eq!(Arc::strong_count(list1.head.as_ref().unwrap()), 1);
 
     list1.concat_mut(&list2);
 
@@ -28,8 +39,350 @@ fn concat_mut() {
     let mut list3 = purse_list!['!'];
 
     // in a singleton list, tail is a clone of head
-    assert_eq!(Arc::strong_count(&list2.head.as_ref().unwrap()), 2);
+    assert_eq!(Arc::strong_count(list2.head.as_ref().unwrap()), 2);
 
     list3.concat_mut(&list2);
     assert_eq!(list3, purse_list!['!', 'c', 'd']);
 }
+
+#[test]
+fn concat_shared_suffix() {
+    let shared = purse_list![1, 2];
+    let prepended = shared.prepend(0);
+
+    // the prepended list's head is unique, but its tail nodes are also reachable from `shared`
+    let joined = prepended.concat(&purse_list![3]);
+
+    assert_eq!(joined, purse_list![0, 1, 2, 3]);
+    assert_eq!(shared.clone().into_iter().collect::<Vec<_>>(), vec![1, 2]);
+    assert_eq!(shared, purse_list![1, 2]);
+}
+
+#[test]
+fn concat_claims_tail_once() {
+    use list::node::Arc;
+
+    let base = purse_list![1, 2];
+    let first = base.clone().concat(&purse_list![3]);
+    let second = base.clone().concat(&purse_list![4]);
+
+    // the first concat links in place and shares every node with the base list
+    assert!(Arc::ptr_eq(first.head.as_ref().unwrap(), base.head.as_ref().unwrap()));
+    // the base list's tail is already claimed, so the second has to copy
+    assert!(!Arc::ptr_eq(second.head.as_ref().unwrap(), base.head.as_ref().unwrap()));
+
+    assert_eq!(base, purse_list![1, 2]);
+    assert_eq!(first, purse_list![1, 2, 3]);
+    assert_eq!(second, purse_list![1, 2, 4]);
+    assert_eq!(base.last(), Some(&2));
+}
+
+#[test]
+fn send_and_sync() {
+    fn assert_send_sync<T: Send + Sync + 'static>() {}
+
+    assert_send_sync::<list::List<String>>();
+    assert_send_sync::<iterator::IntoIter<String>>();
+}
+
+#[test]
+fn accessors_on_empty_lists() {
+    let empty = list::List::<u8>::empty();
+    let emptied = empty.clone().concat(&list::List::empty());
+
+    assert_eq!(empty.first(), None);
+    assert_eq!(empty.last(), None);
+    assert_eq!(emptied.last(), None);
+    assert_eq!(empty.into_iter().next(), None);
+}
+
+#[test]
+fn split_off_cuts_unique_link() {
+    use list::node::Arc;
+
+    let mut list = purse_list![1, 2, 3];
+    let rest = list.split_off(1);
+
+    // the suffix is only held by `rest` once the link from the prefix is cut
+    assert_eq!(Arc::strong_count(rest.head.as_ref().unwrap()), 1);
+
+    let head = list.head.clone();
+    let appended = list.concat(&purse_list![9]);
+
+    assert!(Arc::ptr_eq(appended.head.as_ref().unwrap(), head.as_ref().unwrap()));
+    assert_eq!(appended, purse_list![1, 9]);
+    assert_eq!(rest, purse_list![2, 3]);
+}
+
+#[test]
+fn merge_edits_on_both_sides() {
+    let base = purse_list![1, 2, 3, 4, 5, 6];
+    // insert at the front and change the middle
+    let left = purse_list![0, 1, 2, 30, 4, 5, 6];
+    // remove from the middle and append
+    let right = purse_list![1, 2, 3, 4, 6, 7];
+
+    assert_eq!(
+        list::List::merge(&base, &left, &right),
+        Ok(purse_list![0, 1, 2, 30, 4, 6, 7])
+    );
+    assert_eq!(
+        list::List::merge(&base, &right, &left),
+        Ok(purse_list![0, 1, 2, 30, 4, 6, 7])
+    );
+
+    // both sides make the same change
+    assert_eq!(list::List::merge(&base, &left, &left), Ok(left.clone()));
+
+    // both sides change the last element differently
+    let other = purse_list![1, 2, 3, 4, 5, 8];
+    let conflicts = list::List::merge(&base, &right, &other).unwrap_err();
+
+    assert_eq!(conflicts.len(), 1);
+    assert_eq!(conflicts[0].index, 4);
+    assert_eq!(conflicts[0].base, purse_list![5, 6]);
+    assert_eq!(conflicts[0].left, purse_list![6, 7]);
+    assert_eq!(conflicts[0].right, purse_list![5, 8]);
+}
+
+#[test]
+fn tail_shares_nodes() {
+    use list::node::Arc;
+
+    let list = purse_list![1, 2, 3];
+    // claims the link after the last node, which `list` and its tail must not see
+    let _extended = list.clone().concat(&purse_list![4]);
+    let tail = list.tail();
+
+    assert!(Arc::ptr_eq(tail.head.as_ref().unwrap(), list.head.as_ref().unwrap().next().unwrap()));
+    assert!(Arc::ptr_eq(tail.tail.as_ref().unwrap(), list.tail.as_ref().unwrap()));
+    assert_eq!(tail.len(), 2);
+    assert_eq!(tail, purse_list![2, 3]);
+    assert_eq!(tail.tail().tail(), list::List::empty());
+}
+
+#[test]
+fn top_k_with_huge_k() {
+    let list = purse_list![2, 3, 1];
+
+    assert_eq!(list.top_k(usize::MAX), purse_list![3, 2, 1]);
+    assert_eq!(list.partial_sort(usize::MAX), purse_list![1, 2, 3]);
+    assert_eq!(list.partial_sort(usize::MAX / 2), purse_list![1, 2, 3]);
+}
+
+#[test]
+fn update_shares_suffix() {
+    let list = purse_list![1, 2, 3, 4];
+    let updated = list.update(1, 20).unwrap();
+
+    assert_eq!(updated, purse_list![1, 20, 3, 4]);
+    assert!(updated.walker().nth(2).unwrap().same_node(&list.walker().nth(2).unwrap()));
+    assert!(!updated.walker().next().unwrap().same_node(&list.walker().next().unwrap()));
+    assert_eq!(updated.last(), Some(&4));
+    assert_eq!(list.update(4, 50), None);
+    assert_eq!(list::List::<i32>::empty().update(0, 1), None);
+}
+
+#[test]
+fn insert_and_remove_share_suffix() {
+    let list = purse_list![1, 2, 3];
+    let inserted = list.insert(1, 10).unwrap();
+    let removed = list.remove(0).unwrap();
+
+    assert_eq!(inserted, purse_list![1, 10, 2, 3]);
+    assert!(inserted.walker().nth(2).unwrap().same_node(&list.walker().nth(1).unwrap()));
+    assert!(removed.walker().next().unwrap().same_node(&list.walker().nth(1).unwrap()));
+    assert_eq!(list.insert(3, 4).unwrap().last(), Some(&4));
+    assert_eq!(list::List::<i32>::empty().insert(0, 1), Some(purse_list![1]));
+    assert_eq!(list::List::<i32>::empty().remove(0), None);
+}
+
+#[test]
+fn unique_by_key_shares_suffix() {
+    use KeepPolicy;
+
+    let list = purse_list![1, 2, 1, 3, 4];
+    let first = list.unique_by_key(|&x| x, KeepPolicy::First);
+    let last = list.unique_by_key(|&x| x, KeepPolicy::Last);
+
+    assert_eq!(first, purse_list![1, 2, 3, 4]);
+    assert_eq!(last, purse_list![2, 1, 3, 4]);
+    assert!(first.walker().nth(2).unwrap().same_node(&list.walker().nth(3).unwrap()));
+    assert!(last.walker().nth(1).unwrap().same_node(&list.walker().nth(2).unwrap()));
+    assert_eq!(first.last(), Some(&4));
+}
+
+#[test]
+fn drop_long_list() {
+    let list: list::List<u32> = (0..1_000_000).collect();
+    let suffix = list.suffix(500_000);
+
+    drop(list);
+
+    assert_eq!(suffix.len(), 500_000);
+    assert_eq!(suffix.reverse().last(), Some(&500_000));
+}
+
+#[test]
+fn filter_shares_passing_suffix() {
+    let list = purse_list![1, 2, 3, 4];
+    let filtered = list.filter(|&x| x != 2);
+
+    assert_eq!(filtered, purse_list![1, 3, 4]);
+    assert!(filtered.walker().nth(1).unwrap().same_node(&list.walker().nth(2).unwrap()));
+
+    let all = list.filter(|_| true);
+
+    assert!(all.walker().next().unwrap().same_node(&list.walker().next().unwrap()));
+    assert_eq!(list.filter(|_| false), list::List::empty());
+}
+
+#[test]
+fn garbage_counts_hidden_nodes_once() {
+    let base = purse_list![1, 2];
+    let short = base.tail();
+    let joined = base.clone().concat(&purse_list![3, 4]);
+    let longer = joined.clone().concat(&purse_list![5]);
+
+    drop(joined);
+
+    assert_eq!(list::List::garbage(&[&base, &short]).nodes, 3);
+    assert_eq!(list::List::garbage(&[&base, &short, &longer]).nodes, 0);
+
+    drop(longer);
+
+    assert_eq!(list::List::garbage(&[&base, &short]).nodes, 3);
+    assert_eq!(list::List::<i32>::garbage(&[]).bytes, 0);
+}
+
+#[test]
+fn take_copies_and_skip_shares() {
+    let list = purse_list![1, 2, 3];
+    let taken = list.take(2);
+    let skipped = list.skip(1);
+
+    assert_eq!(taken.last(), Some(&2));
+    assert!(!taken.walker().next().unwrap().same_node(&list.walker().next().unwrap()));
+    assert!(skipped.walker().next().unwrap().same_node(&list.walker().nth(1).unwrap()));
+    assert_eq!(skipped.last(), Some(&3));
+}
+
+#[test]
+fn span_shares_dropped_suffix() {
+    let list = purse_list![1, 2, 3];
+    let (prefix, rest) = list.span(|&x| x == 1);
+
+    assert_eq!(prefix, purse_list![1]);
+    assert!(rest.walker().next().unwrap().same_node(&list.walker().nth(1).unwrap()));
+    assert!(list.drop_while(|&x| x < 3).walker().next().unwrap().is_shared());
+    assert_eq!(list.drop_while(|_| true).len(), 0);
+}
+
+#[test]
+#[should_panic(expected = "list length overflow")]
+fn create_checks_length() {
+    let mut huge = purse_list![1];
+    huge.size = usize::MAX;
+
+    huge.prepend(0);
+}
+
+#[test]
+fn concat_checks_length_before_linking() {
+    use std::panic::{self, AssertUnwindSafe};
+
+    let left = purse_list![1, 2];
+    let mut right = purse_list![3];
+    right.size = usize::MAX;
+
+    let result = panic::catch_unwind(AssertUnwindSafe(|| left.clone().concat(&right)));
+
+    assert!(result.is_err());
+
+    // the tail is still free, so the next concat links in place
+    let joined = left.clone().concat(&purse_list![4]);
+
+    assert_eq!(joined, purse_list![1, 2, 4]);
+    assert!(joined.walker().next().unwrap().same_node(&left.walker().next().unwrap()));
+}
+
+#[test]
+fn concurrent_cross_concat_never_links_a_loop() {
+    use std::sync::{Arc, Barrier};
+    use std::thread;
+
+    let token = Arc::new(());
+
+    for _ in 0..200 {
+        let a: list::List<Arc<()>> = std::iter::repeat_n(token.clone(), 2).collect();
+        let b: list::List<Arc<()>> = std::iter::repeat_n(token.clone(), 2).collect();
+        let barrier = Arc::new(Barrier::new(2));
+
+        let spawn = |left: list::List<Arc<()>>, right: list::List<Arc<()>>| {
+            let barrier = barrier.clone();
+
+            thread::spawn(move || {
+                barrier.wait();
+                left.concat(&right).len()
+            })
+        };
+
+        let ab = spawn(a.clone(), b.clone());
+        let ba = spawn(b, a);
+
+        assert_eq!(ab.join().unwrap(), 4);
+        assert_eq!(ba.join().unwrap(), 4);
+    }
+
+    assert_eq!(Arc::strong_count(&token), 1);
+}
+
+#[test]
+fn concat_backs_off_from_claimed_tail() {
+    let a = purse_list![1, 2];
+    let b = purse_list![3, 4];
+
+    // as if another thread were in the middle of `a.concat(&b)`: a's last node is claimed but
+    // not linked yet
+    assert!(a.tail.as_ref().unwrap().try_claim());
+
+    let ba = b.clone().concat(&a);
+
+    assert_eq!(ba, purse_list![3, 4, 1, 2]);
+    assert!(!ba.walker().next().unwrap().same_node(&b.walker().next().unwrap()));
+    assert!(b.tail.as_ref().unwrap().next().is_none());
+}
+
+#[test]
+fn concat_never_links_a_loop() {
+    use std::sync::Arc;
+
+    let token = Arc::new(());
+    let full: list::List<Arc<()>> = std::iter::repeat_n(token.clone(), 3).collect();
+
+    // a view of the first two nodes, which are still linked to the third
+    let mut front = full.clone();
+    front.split_off(2);
+
+    let back = full.suffix(2);
+    let joined = back.concat(&front);
+
+    assert_eq!(joined.len(), 3);
+
+    drop((full, front, joined));
+
+    assert_eq!(Arc::strong_count(&token), 1);
+}
+
+#[test]
+fn sort_is_stable() {
+    let pairs: Vec<(u8, usize)> = (0..100).map(|i| ((i * 37 % 11) as u8, i)).collect();
+    let list: list::List<(u8, usize)> = pairs.iter().cloned().collect();
+
+    let mut expected = pairs.clone();
+    expected.sort_by_key(|pair| pair.0);
+
+    assert_eq!(list.sort_by_key(|pair| pair.0).iter().cloned().collect::<Vec<_>>(), expected);
+    assert_eq!(list.sort().last(), Some(&(10, 96)));
+    assert_eq!(list::List::<u8>::empty().sort().len(), 0);
+}
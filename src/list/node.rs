@@ -1,92 +1,157 @@
-use std::sync::{Arc, Weak};
+use std::sync::OnceLock;
 use std::sync::atomic::{AtomicBool, Ordering};
-use std::cell::UnsafeCell;
-use std::fmt;
 
-use List;
+use instrument;
 
-pub type Link<T> = Option<Arc<UnsafeCell<Node<T>>>>;
-pub type WeakLink<T> = Option<Weak<UnsafeCell<Node<T>>>>;
+#[cfg(not(feature = "triomphe"))]
+pub use std::sync::Arc;
+#[cfg(feature = "triomphe")]
+pub use triomphe::Arc;
 
-pub fn new_link<T: Clone>(node: Node<T>) -> Link<T> {
-    Some(Arc::new(UnsafeCell::new(node)))
-}
+pub type Link<T> = Option<Arc<Node<T>>>;
 
-pub fn get_unwrapped_link_node<T: Clone>(link: &Arc<UnsafeCell<Node<T>>>) -> &Node<T> {
-    get_unwrapped_link_node_mut(link)
-}
-
-pub fn get_unwrapped_link_node_mut<T: Clone>(link: &Arc<UnsafeCell<Node<T>>>) -> &mut Node<T> {
-    unsafe { &mut *link.get() }
-}
+pub fn new_link<T: Clone>(node: Node<T>) -> Link<T> {
+    instrument::node_allocated();
 
-pub fn get_link_node<T: Clone>(link: &Link<T>) -> &Node<T> {
-    get_unwrapped_link_node(link.as_ref().unwrap())
+    Some(Arc::new(node))
 }
 
-#[derive(Clone)]
+// A node only ever gets a successor once: either when it's created or when a list ending at it is
+// concatenated in place. Lists keep their own length, so nodes linked after the end of a list are
+// never visible through it.
+//
+// Before linking a successor, a list claims the node. The claim is visible to other threads as
+// soon as it's made, before the successor is set, so that a concatenation can tell that the node
+// is about to get one.
 pub struct Node<T: Clone> {
     pub data: T,
-    pub next: List<T>,
-    mutating: Arc<AtomicBool>,
+    next: OnceLock<Arc<Node<T>>>,
+    claimed: AtomicBool,
 }
 
 impl<T: Clone> Node<T> {
-    pub fn new(data: T, next: List<T>) -> Self {
+    pub fn new(data: T, next: Link<T>) -> Self {
         Node {
-            data: data,
-            next: next,
-            mutating: Arc::new(AtomicBool::new(false)),
+            data,
+            claimed: AtomicBool::new(next.is_some()),
+            next: next.map_or_else(OnceLock::new, OnceLock::from),
         }
     }
 
-    pub fn index(&self, index: usize) -> &T {
-        match index {
-            0 => &self.data,
-            _ => {
-                assert!(self.next.size > 0);
+    pub fn next(&self) -> Option<&Arc<Node<T>>> {
+        self.next.get()
+    }
+
+    pub fn next_mut(&mut self) -> Option<&mut Arc<Node<T>>> {
+        self.next.get_mut()
+    }
+
+    // Removes the successor of this node so that it can be linked again.
+    pub fn unlink(&mut self) -> Link<T> {
+        *self.claimed.get_mut() = false;
+
+        self.next.take()
+    }
+
+    // Claims the node for linking a successor. Returns false if another list has already
+    // claimed it.
+    pub fn try_claim(&self) -> bool {
+        !self.claimed.swap(true, Ordering::SeqCst)
+    }
+
+    // Returns true if the node has a successor or a list is about to link one.
+    pub fn is_claimed(&self) -> bool {
+        self.claimed.load(Ordering::SeqCst)
+    }
+
+    // Gives up a claim without linking anything.
+    pub fn release(&self) {
+        self.claimed.store(false, Ordering::SeqCst);
+    }
+
+    // Sets the successor of a node claimed with `try_claim`.
+    pub fn link(&self, link: &Arc<Node<T>>) {
+        let linked = self.next.set(link.clone()).is_ok();
+
+        debug_assert!(linked, "linked a node that was already linked");
+    }
 
-                let node = &get_link_node(&self.next.head);
+    // Sets the successor of this node if it doesn't have one yet. Returns false if another list
+    // has already claimed it.
+    pub fn try_link(&self, link: &Arc<Node<T>>) -> bool {
+        let claimed = self.try_claim();
 
-                node.index(index - 1)
-            }
+        if claimed {
+            self.link(link);
         }
+
+        claimed
     }
+}
+
+// Returns true if following the links from `link` leads to `target`. Only used to detect loops
+// in debug builds, since it walks past the end of any list.
+pub fn reaches<T: Clone>(link: &Link<T>, target: &Arc<Node<T>>) -> bool {
+    let mut link = link.as_ref();
+
+    while let Some(node) = link {
+        if Arc::ptr_eq(node, target) {
+            return true;
+        }
 
-    pub fn concat_list(&self, start: &Link<T>, list: &List<T>) -> List<T> {
-        self.next.head.as_ref().map_or(
-            List::create(
-                self.data.clone(),
-                list.clone(),
-            ),
-            |link| {
-                let node = get_unwrapped_link_node(&link);
-
-                List::create(self.data.clone(), node.concat_list(start, list))
-            },
-        )
+        link = node.next();
     }
 
-    pub fn try_mutate(&self) -> bool {
-        !self.mutating.compare_and_swap(
-            false,
-            true,
-            Ordering::Relaxed,
-        )
+    false
+}
+
+// Dropping a node would otherwise drop its successor recursively, which overflows the stack on
+// long lists. Instead, successors that aren't shared with anything else are dropped one by one.
+impl<T: Clone> Drop for Node<T> {
+    fn drop(&mut self) {
+        let mut next = self.next.take();
+
+        while let Some(link) = next {
+            next = match Arc::try_unwrap(link) {
+                Ok(mut node) => node.next.take(),
+                Err(_) => None,
+            };
+        }
     }
+}
 
-    pub fn end_mutate(&self) {
-        self.mutating.store(false, Ordering::Relaxed);
+// Iterates over the first `remaining` nodes starting from a link.
+pub struct Nodes<'a, T: 'a + Clone> {
+    link: Option<&'a Arc<Node<T>>>,
+    remaining: usize,
+}
+
+impl<'a, T: 'a + Clone> Nodes<'a, T> {
+    pub fn new(link: &'a Link<T>, remaining: usize) -> Self {
+        Nodes {
+            link: link.as_ref(),
+            remaining,
+        }
     }
 }
 
-impl<T: Clone + fmt::Debug> fmt::Debug for Node<T> {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        self.next.head.as_ref().map_or(
-            write!(f, "{:?}", self.data),
-            |ref link| {
-                write!(f, "{:?}, {:?}", self.data, get_unwrapped_link_node(&link))
-            },
-        )
+impl<'a, T: 'a + Clone> Iterator for Nodes<'a, T> {
+    type Item = &'a Arc<Node<T>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+
+        let link = self.link?;
+
+        self.remaining -= 1;
+        self.link = link.next();
+
+        Some(link)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
     }
 }
@@ -0,0 +1,62 @@
+use rand::Rng;
+
+use List;
+
+impl<T: Clone> List<T> {
+    /// Picks an element at random, with a probability proportional to the weight `weight` gives
+    /// it. Elements with a weight of zero or less are never picked. Returns None if the list is
+    /// empty or no element has a positive, finite total weight.
+    ///
+    /// This takes two passes over the list, so it's O(n).
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # extern crate rand;
+    /// # fn main() {
+    /// use rand::SeedableRng;
+    /// use rand::rngs::StdRng;
+    ///
+    /// let loot = purse_list![("sword", 1.0), ("nothing", 0.0), ("potion", 9.0)];
+    /// let mut rng = StdRng::seed_from_u64(7);
+    ///
+    /// for _ in 0..100 {
+    ///     let &(item, _) = loot.choose_weighted(&mut rng, |&(_, weight)| weight).unwrap();
+    ///
+    ///     assert!(item != "nothing");
+    /// }
+    /// # }
+    /// ```
+    pub fn choose_weighted<R, F>(&self, rng: &mut R, weight: F) -> Option<&T>
+    where
+        R: Rng + ?Sized,
+        F: Fn(&T) -> f64,
+    {
+        let positive = |elem: &T| weight(elem).max(0.0);
+        let total: f64 = self.iter().map(&positive).sum();
+
+        if total <= 0.0 || !total.is_finite() {
+            return None;
+        }
+
+        let mut remaining = rng.random::<f64>() * total;
+        let mut chosen = None;
+
+        for elem in self.iter() {
+            let weight = positive(elem);
+
+            if weight > 0.0 {
+                chosen = Some(elem);
+
+                if remaining < weight {
+                    break;
+                }
+
+                remaining -= weight;
+            }
+        }
+
+        chosen
+    }
+}
@@ -0,0 +1,39 @@
+use std::borrow::Cow;
+
+use schemars::{JsonSchema, Schema, SchemaGenerator};
+
+use List;
+
+/// Lists are described by the same schema as a JSON array of their elements.
+///
+/// #Examples
+///
+/// ```
+/// # extern crate purse;
+/// # #[macro_use] extern crate schemars;
+/// # fn main() {
+/// use purse::List;
+///
+/// let schema = schema_for!(List<i32>);
+///
+/// assert_eq!(schema.get("type").and_then(|t| t.as_str()), Some("array"));
+/// assert_eq!(schema, schema_for!(Vec<i32>));
+/// # }
+/// ```
+impl<T: Clone + JsonSchema> JsonSchema for List<T> {
+    fn inline_schema() -> bool {
+        <Vec<T>>::inline_schema()
+    }
+
+    fn schema_name() -> Cow<'static, str> {
+        <Vec<T>>::schema_name()
+    }
+
+    fn schema_id() -> Cow<'static, str> {
+        <Vec<T>>::schema_id()
+    }
+
+    fn json_schema(generator: &mut SchemaGenerator) -> Schema {
+        <Vec<T>>::json_schema(generator)
+    }
+}
@@ -0,0 +1,50 @@
+use std::iter::FromIterator;
+use std::sync::Arc;
+
+use List;
+use instrument;
+
+/// Lists of `Arc`s are cheap to rebuild whatever the size of the elements, since copying a node
+/// only clones the `Arc`.
+impl<T> List<Arc<T>> {
+    /// Creates a list from an iterator of values, wrapping each value in an `Arc`.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::List;
+    /// use std::sync::Arc;
+    ///
+    /// let list = List::from_values(vec![String::from("a"), String::from("b")]);
+    ///
+    /// assert_eq!(list[1], Arc::new(String::from("b")));
+    /// ```
+    pub fn from_values<I: IntoIterator<Item = T>>(values: I) -> Self {
+        List::from_iter(values.into_iter().map(Arc::new))
+    }
+
+    /// Creates a new list by passing each element to `f`. Elements for which `f` returns `Some`
+    /// are replaced by the new value; the others keep their existing `Arc`, so they are shared
+    /// with this list rather than cloned.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::List;
+    /// use std::sync::Arc;
+    ///
+    /// let list = List::from_values(vec![1, 2, 3]);
+    /// let evens_doubled = list.map_arc(|x| if x % 2 == 0 { Some(x * 2) } else { None });
+    ///
+    /// assert_eq!(evens_doubled, List::from_values(vec![1, 4, 3]));
+    /// assert!(Arc::ptr_eq(&list[0], &evens_doubled[0]));
+    /// ```
+    pub fn map_arc<F>(&self, mut f: F) -> Self
+    where
+        F: FnMut(&T) -> Option<T>,
+    {
+        self.iter()
+            .map(|elem| f(elem).map_or_else(|| instrument::clone_counted(elem), Arc::new))
+            .collect()
+    }
+}
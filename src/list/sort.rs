@@ -0,0 +1,158 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::BinaryHeap;
+use std::iter::FromIterator;
+use std::mem;
+
+use List;
+use instrument;
+
+impl<T: Clone + Ord> List<T> {
+    /// Creates a new list with the elements in ascending order. The sort is stable, so equal
+    /// elements keep their relative order.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![3, 1, 2];
+    ///
+    /// assert_eq!(list.sort(), purse_list![1, 2, 3]);
+    /// assert_eq!(list, purse_list![3, 1, 2]);
+    /// # }
+    /// ```
+    pub fn sort(&self) -> Self {
+        self.sort_by(Ord::cmp)
+    }
+
+    /// Creates a list of the `k` largest elements, largest first, without sorting the whole list.
+    /// Keeps a heap of at most `k` elements, so it takes O(n log k) time.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let scores = purse_list![40, 95, 12, 78, 95, 3];
+    ///
+    /// assert_eq!(scores.top_k(3), purse_list![95, 95, 78]);
+    /// assert_eq!(scores.top_k(10).len(), 6);
+    /// # }
+    /// ```
+    pub fn top_k(&self, k: usize) -> Self {
+        let mut heap = BinaryHeap::with_capacity(k.min(self.size).saturating_add(1));
+
+        for elem in self.iter() {
+            heap.push(Reverse(elem));
+
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        // ascending order of Reverse is descending order of the elements
+        let sorted = heap.into_sorted_vec();
+
+        List::from_iter(sorted.into_iter().map(|elem| instrument::clone_counted(elem.0)))
+    }
+
+    /// Creates a list of the `k` smallest elements in ascending order, without sorting the whole
+    /// list. Keeps a heap of at most `k` elements, so it takes O(n log k) time.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![5, 1, 4, 2, 3];
+    ///
+    /// assert_eq!(list.partial_sort(2), purse_list![1, 2]);
+    /// assert_eq!(list.partial_sort(0), purse_list![]);
+    /// # }
+    /// ```
+    pub fn partial_sort(&self, k: usize) -> Self {
+        let mut heap = BinaryHeap::with_capacity(k.min(self.size).saturating_add(1));
+
+        for elem in self.iter() {
+            heap.push(elem);
+
+            if heap.len() > k {
+                heap.pop();
+            }
+        }
+
+        let sorted = heap.into_sorted_vec();
+
+        List::from_iter(sorted.into_iter().map(instrument::clone_counted))
+    }
+}
+
+impl<T: Clone> List<T> {
+    /// Creates a new list with the elements sorted by the given comparison function. The sort is
+    /// stable, and is an iterative bottom-up merge sort, so it takes O(n log n) time and doesn't
+    /// recurse.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 3, 2];
+    ///
+    /// assert_eq!(list.sort_by(|a, b| b.cmp(a)), purse_list![3, 2, 1]);
+    /// # }
+    /// ```
+    pub fn sort_by<F: FnMut(&T, &T) -> Ordering>(&self, mut compare: F) -> Self {
+        let mut sorted: Vec<&T> = self.iter().collect();
+        let mut merged = Vec::with_capacity(sorted.len());
+        let len = sorted.len();
+        let mut width = 1;
+
+        // merge neighbouring sorted runs of `width` elements until a single run is left
+        while width < len {
+            merged.clear();
+
+            for start in (0..len).step_by(2 * width) {
+                let mid = len.min(start + width);
+                let end = len.min(start + 2 * width);
+                let (mut i, mut j) = (start, mid);
+
+                while i < mid && j < end {
+                    // take from the left run on ties to keep the sort stable
+                    if compare(sorted[j], sorted[i]) == Ordering::Less {
+                        merged.push(sorted[j]);
+                        j += 1;
+                    } else {
+                        merged.push(sorted[i]);
+                        i += 1;
+                    }
+                }
+
+                merged.extend_from_slice(&sorted[i..mid]);
+                merged.extend_from_slice(&sorted[j..end]);
+            }
+
+            mem::swap(&mut sorted, &mut merged);
+            width *= 2;
+        }
+
+        sorted.into_iter().map(instrument::clone_counted).collect()
+    }
+
+    /// Creates a new list with the elements sorted by the keys returned by `f`. The sort is
+    /// stable, so elements with equal keys keep their relative order.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let words = purse_list!["bb", "a", "ccc", "d"];
+    ///
+    /// assert_eq!(words.sort_by_key(|word| word.len()), purse_list!["a", "d", "bb", "ccc"]);
+    /// # }
+    /// ```
+    pub fn sort_by_key<K: Ord, F: FnMut(&T) -> K>(&self, mut f: F) -> Self {
+        self.sort_by(|a, b| f(a).cmp(&f(b)))
+    }
+}
@@ -0,0 +1,64 @@
+use std::collections::HashSet;
+use std::hash::Hash;
+
+use List;
+
+/// Which element `List::unique_by_key` keeps when several elements have the same key.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum KeepPolicy {
+    /// Keep the first element with each key.
+    First,
+    /// Keep the last element with each key.
+    Last,
+}
+
+impl<T: Clone> List<T> {
+    /// Creates a list with one element for each key returned by `f`, keeping either the first or
+    /// the last element with that key. The kept elements stay in their original order, and the
+    /// elements after the last dropped one are shared with this list.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::KeepPolicy;
+    ///
+    /// let log = purse_list![("a", 1), ("b", 2), ("a", 3), ("c", 4)];
+    ///
+    /// assert_eq!(
+    ///     log.unique_by_key(|entry| entry.0, KeepPolicy::First),
+    ///     purse_list![("a", 1), ("b", 2), ("c", 4)]
+    /// );
+    /// assert_eq!(
+    ///     log.unique_by_key(|entry| entry.0, KeepPolicy::Last),
+    ///     purse_list![("b", 2), ("a", 3), ("c", 4)]
+    /// );
+    /// # }
+    /// ```
+    #[track_caller]
+    pub fn unique_by_key<K, F>(&self, mut f: F, policy: KeepPolicy) -> Self
+    where
+        K: Hash + Eq,
+        F: FnMut(&T) -> K,
+    {
+        let elems: Vec<&T> = self.iter().collect();
+        let mut seen = HashSet::new();
+        let mut keep = vec![false; elems.len()];
+
+        match policy {
+            KeepPolicy::First => {
+                for (index, elem) in elems.iter().enumerate() {
+                    keep[index] = seen.insert(f(elem));
+                }
+            }
+            KeepPolicy::Last => {
+                for (index, elem) in elems.iter().enumerate().rev() {
+                    keep[index] = seen.insert(f(elem));
+                }
+            }
+        }
+
+        self.select("unique_by_key", &keep)
+    }
+}
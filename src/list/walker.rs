@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+use std::mem;
+
+use List;
+
+use super::node::{Arc, Node, Nodes};
+
+/// An iterator over the nodes of a list, created by `List::walker`.
+pub struct Walker<'a, T: 'a + Clone> {
+    list: &'a List<T>,
+    nodes: Nodes<'a, T>,
+    index: usize,
+    shared: bool,
+}
+
+/// A read-only view of one node of a list, for inspecting how lists share structure.
+pub struct NodeRef<'a, T: 'a + Clone> {
+    list: &'a List<T>,
+    node: &'a Arc<Node<T>>,
+    index: usize,
+    shared: bool,
+}
+
+/// Nodes that are kept alive by a set of lists without being part of any of them, found by
+/// `List::garbage`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Garbage {
+    /// The number of retained nodes.
+    pub nodes: usize,
+    /// The size of the retained nodes, not counting reference counts or memory owned by the
+    /// elements.
+    pub bytes: usize,
+}
+
+impl<T: Clone> List<T> {
+    /// Returns an iterator over the nodes of the list, for inspecting how it shares structure
+    /// with other lists.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list![1, 2, 3];
+    /// let prepended = list.prepend(0);
+    ///
+    /// let shared: Vec<bool> = prepended.walker().map(|node| node.is_shared()).collect();
+    ///
+    /// assert_eq!(shared, vec![false, true, true, true]);
+    /// assert!(prepended.walker().nth(1).unwrap().same_node(&list.walker().next().unwrap()));
+    /// # }
+    /// ```
+    pub fn walker(&self) -> Walker<'_, T> {
+        Walker {
+            list: self,
+            nodes: self.nodes(),
+            index: 0,
+            shared: false,
+        }
+    }
+
+    /// Reports the nodes that the given lists keep alive without any of them containing those
+    /// nodes. This happens when a list was concatenated in place after one of them: its nodes
+    /// stay linked after the last node even once every list that contained them is dropped.
+    ///
+    /// `roots` should be all the lists that are still in use, since nodes that only other lists
+    /// contain are counted as garbage. Rebuilding a list, e.g. with `map(Clone::clone)`, gives it
+    /// fresh nodes with nothing linked after them.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// use purse::List;
+    ///
+    /// let base = purse_list![1, 2];
+    /// let extra = purse_list![3, 4, 5];
+    /// let joined = base.clone().concat(&extra);
+    ///
+    /// assert_eq!(List::garbage(&[&base, &joined]).nodes, 0);
+    ///
+    /// drop((joined, extra));
+    ///
+    /// assert_eq!(List::garbage(&[&base]).nodes, 3);
+    /// assert_eq!(List::garbage(&[&base.map(Clone::clone)]).nodes, 0);
+    /// # }
+    /// ```
+    pub fn garbage(roots: &[&List<T>]) -> Garbage {
+        let visible: HashSet<*const Node<T>> = roots
+            .iter()
+            .flat_map(|root| root.nodes())
+            .map(|node| &**node as *const Node<T>)
+            .collect();
+        let mut retained = HashSet::new();
+
+        // within a list, only the link after its last node leads outside of it
+        for root in roots {
+            let mut link = root.tail.as_ref().and_then(|tail| tail.next());
+
+            while let Some(node) = link {
+                let ptr = &**node as *const Node<T>;
+
+                if visible.contains(&ptr) || !retained.insert(ptr) {
+                    break;
+                }
+
+                link = node.next();
+            }
+        }
+
+        Garbage {
+            nodes: retained.len(),
+            bytes: retained.len() * mem::size_of::<Node<T>>(),
+        }
+    }
+}
+
+impl<'a, T: 'a + Clone> Iterator for Walker<'a, T> {
+    type Item = NodeRef<'a, T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.nodes.next()?;
+        let index = self.index;
+
+        let is_tail = self.list
+            .tail
+            .as_ref()
+            .is_some_and(|tail| Arc::ptr_eq(tail, node));
+
+        // one reference from the head of the list or the previous node, plus the list's tail.
+        // Anything more means another list can reach this node and every node after it.
+        self.shared = self.shared || Arc::strong_count(node) > 1 + is_tail as usize;
+        self.index += 1;
+
+        Some(NodeRef {
+            list: self.list,
+            node,
+            index,
+            shared: self.shared,
+        })
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.nodes.size_hint()
+    }
+}
+
+impl<'a, T: 'a + Clone> NodeRef<'a, T> {
+    /// Returns the element stored in the node.
+    pub fn data(&self) -> &'a T {
+        &self.node.data
+    }
+
+    /// Returns the position of the node in the list being walked.
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the number of strong references to the node, from lists and from other nodes.
+    pub fn strong_count(&self) -> usize {
+        Arc::strong_count(self.node)
+    }
+
+    /// Returns true if something other than the list being walked can reach the node, either
+    /// directly or through an earlier node of the list.
+    pub fn is_shared(&self) -> bool {
+        self.shared
+    }
+
+    /// Returns true if both refer to the same node, which may be reached through different lists.
+    pub fn same_node(&self, other: &NodeRef<T>) -> bool {
+        Arc::ptr_eq(self.node, other.node)
+    }
+
+    /// Returns the part of the walked list that starts at this node, sharing its nodes.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// # #[macro_use] extern crate purse;
+    /// # fn main() {
+    /// let list = purse_list!['a', 'b', 'c'];
+    /// let node = list.walker().nth(1).unwrap();
+    ///
+    /// assert_eq!(*node.data(), 'b');
+    /// assert_eq!(node.rest(), purse_list!['b', 'c']);
+    /// # }
+    /// ```
+    pub fn rest(&self) -> List<T> {
+        self.list.suffix(self.index)
+    }
+}
@@ -0,0 +1,23 @@
+//! Convenience re-exports of the collection types and macros.
+//!
+//! #Examples
+//!
+//! ```
+//! use purse::prelude::*;
+//!
+//! static_purse_list! {
+//!     static DIGITS: List<i32> = [1, 2];
+//! }
+//!
+//! let list: List<i32> = purse_list![1, 2, 3];
+//!
+//! assert_eq!(list.len(), 3);
+//! assert_eq!(*DIGITS, purse_list![1, 2]);
+//! ```
+
+pub use List;
+pub use AssocList;
+pub use RangeSet;
+pub use Lazy;
+pub use purse_list;
+pub use static_purse_list;
@@ -0,0 +1,217 @@
+use std::fmt;
+use std::ops::Range;
+
+use List;
+use instrument;
+use list::iterator;
+
+/// A persistent set of values stored as disjoint, half-open ranges.
+///
+/// The ranges are kept sorted and coalesced: overlapping or touching ranges are merged when they're
+/// inserted, so iterating yields the fewest ranges that cover the set. Inserting or removing a
+/// range copies the ranges before it and shares the ones after it with the previous version, which
+/// stays unchanged. Operations are linear in the number of ranges, so this suits sets that stay
+/// fragmented into a modest number of pieces, like the blocks of IDs handed out by an allocator.
+///
+/// Examples
+///
+/// ```
+/// use purse::RangeSet;
+///
+/// let committed = RangeSet::empty().insert_range(0..10);
+/// let speculative = committed.insert_range(10..20).remove_range(5..8);
+///
+/// assert_eq!(speculative.iter().cloned().collect::<Vec<_>>(), vec![0..5, 8..20]);
+/// assert_eq!(committed.iter().cloned().collect::<Vec<_>>(), vec![0..10]);
+/// ```
+#[derive(Clone)]
+pub struct RangeSet<T: Clone> {
+    ranges: List<Range<T>>,
+}
+
+impl<T: Clone + Ord> RangeSet<T> {
+    /// Creates a set without any ranges.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::RangeSet;
+    ///
+    /// let set: RangeSet<u32> = RangeSet::empty();
+    ///
+    /// assert!(set.is_empty());
+    /// ```
+    pub const fn empty() -> Self {
+        RangeSet { ranges: List::empty() }
+    }
+
+    /// Creates a new set that also contains the values in `range`. Ranges that overlap or touch it
+    /// are merged into one. Inserting an empty range returns the set unchanged.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::RangeSet;
+    ///
+    /// let set = RangeSet::empty().insert_range(0..4).insert_range(8..12);
+    /// let joined = set.insert_range(4..8);
+    ///
+    /// assert_eq!(set.len(), 2);
+    /// assert_eq!(joined.iter().collect::<Vec<_>>(), vec![&(0..12)]);
+    /// ```
+    pub fn insert_range(&self, range: Range<T>) -> Self {
+        if range.start >= range.end ||
+            self.ranges
+                .iter()
+                .take_while(|r| r.start <= range.start)
+                .any(|r| range.end <= r.end)
+        {
+            return self.clone();
+        }
+
+        let before = self.ranges.iter().take_while(|r| r.end < range.start).count();
+        let mut merged = range;
+        let mut rest = before;
+
+        for r in self.ranges.iter().skip(before) {
+            if r.start > merged.end {
+                break;
+            }
+
+            if r.start < merged.start {
+                merged.start = r.start.clone();
+            }
+            if r.end > merged.end {
+                merged.end = r.end.clone();
+            }
+
+            rest += 1;
+        }
+
+        let prefix: List<Range<T>> = self.ranges
+            .iter()
+            .take(before)
+            .map(instrument::clone_counted)
+            .chain(Some(merged))
+            .collect();
+
+        RangeSet { ranges: prefix.concat(&self.ranges.suffix(rest)) }
+    }
+
+    /// Creates a new set without the values in `range`. Ranges that only partly overlap it are
+    /// trimmed, and a range that covers it on both sides is split in two.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::RangeSet;
+    ///
+    /// let set = RangeSet::empty().insert_range(0..10).insert_range(20..30);
+    /// let removed = set.remove_range(5..25);
+    /// let split = set.remove_range(2..4);
+    ///
+    /// assert_eq!(removed.iter().cloned().collect::<Vec<_>>(), vec![0..5, 25..30]);
+    /// assert_eq!(split.iter().cloned().collect::<Vec<_>>(), vec![0..2, 4..10, 20..30]);
+    /// ```
+    pub fn remove_range(&self, range: Range<T>) -> Self {
+        if range.start >= range.end {
+            return self.clone();
+        }
+
+        let before = self.ranges.iter().take_while(|r| r.end <= range.start).count();
+        let mut pieces = Vec::new();
+        let mut rest = before;
+
+        for r in self.ranges.iter().skip(before) {
+            if r.start >= range.end {
+                break;
+            }
+
+            // only the first overlapping range can start before `range`, and only the last can
+            // end after it
+            if r.start < range.start {
+                pieces.push(r.start.clone()..range.start.clone());
+            }
+            if r.end > range.end {
+                pieces.push(range.end.clone()..r.end.clone());
+            }
+
+            rest += 1;
+        }
+
+        if rest == before {
+            return self.clone();
+        }
+
+        let prefix: List<Range<T>> = self.ranges
+            .iter()
+            .take(before)
+            .map(instrument::clone_counted)
+            .chain(pieces)
+            .collect();
+
+        RangeSet { ranges: prefix.concat(&self.ranges.suffix(rest)) }
+    }
+
+    /// Returns true if `value` is in one of the ranges.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::RangeSet;
+    ///
+    /// let set = RangeSet::empty().insert_range(10..20);
+    ///
+    /// assert!(set.contains(&10));
+    /// assert!(!set.contains(&20));
+    /// ```
+    pub fn contains(&self, value: &T) -> bool {
+        self.ranges
+            .iter()
+            .take_while(|r| r.start <= *value)
+            .any(|r| *value < r.end)
+    }
+
+    /// Returns the number of disjoint ranges in the set.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Returns true if the set doesn't contain any values.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Returns an iterator over the coalesced ranges, in ascending order.
+    ///
+    /// #Examples
+    ///
+    /// ```
+    /// use purse::RangeSet;
+    ///
+    /// let set = RangeSet::empty().insert_range(5..7).insert_range(1..3).insert_range(3..4);
+    ///
+    /// assert_eq!(set.iter().cloned().collect::<Vec<_>>(), vec![1..4, 5..7]);
+    /// ```
+    pub fn iter(&self) -> iterator::Iter<'_, Range<T>> {
+        self.ranges.iter()
+    }
+}
+
+impl<T: Clone + Ord> Default for RangeSet<T> {
+    fn default() -> Self {
+        RangeSet::empty()
+    }
+}
+
+impl<T: Clone + PartialEq> PartialEq for RangeSet<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.ranges == other.ranges
+    }
+}
+
+impl<T: Clone + fmt::Debug> fmt::Debug for RangeSet<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set().entries(self.ranges.iter()).finish()
+    }
+}